@@ -1,9 +1,13 @@
 use super::*;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone)]
 pub enum Declaration {
     ClassDecl(ClassDecl),
+    EnumDecl(EnumDecl),
     FunDecl(FunDecl),
+    ImportDecl(ImportDecl),
+    RecordDecl(RecordDecl),
     VarDecl(VarDecl),
     Statement(Statement),
 }
@@ -13,15 +17,62 @@ pub struct ClassDecl {
     pub name: String,
     pub super_class: Option<String>,
     pub functions: Vec<Function>,
+    /// `area { ... }`
+    pub getters: Vec<Function>,
+    /// `set area(value) { ... }`
+    pub setters: Vec<Function>,
+    pub statics: Vec<Function>,
+    pub fields: Vec<ClassField>,
 }
 
+#[derive(Clone)]
+pub struct ClassField {
+    pub name: String,
+    pub expr: Option<Expression>,
+}
+
+#[derive(Clone)]
+pub struct EnumDecl {
+    pub name: String,
+    pub variants: Vec<VariantDecl>,
+}
+
+pub type VariantDecl = (String, Vec<String>);
+
 #[derive(Clone)]
 pub struct FunDecl(pub Function);
 
 #[derive(Clone)]
-pub struct VarDecl {
+pub struct ImportDecl {
+    pub tok: Token,
+    pub path: String,
+    pub names: Imports,
+}
+
+#[derive(Clone)]
+pub enum Imports {
+    As(String),
+    Names(Vec<String>),
+}
+
+#[derive(Clone)]
+pub struct RecordDecl {
     pub name: String,
-    pub expr: Option<Expression>,
+    pub fields: Vec<String>,
+}
+
+#[derive(Clone)]
+pub enum VarDecl {
+    Name(String, Option<Expression>),
+    /// `var (a, b) = values;`, always has the values to unpack.
+    Tuple(Vec<String>, Tuple),
+}
+
+#[derive(Clone)]
+pub enum Binding {
+    Name(String),
+    /// `(a, b)`, unpacks a list into one variable per element.
+    Tuple(Vec<String>),
 }
 
 #[derive(Clone)]
@@ -29,16 +80,32 @@ pub struct Function {
     pub name: String,
     pub params: Option<Parameters>,
     pub body: Block,
+    pub kind: FunKind,
+    /// Each is called with the declared function and gives what gets bound.
+    pub decorators: Vec<Expression>,
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum FunKind {
+    #[default]
+    Plain,
+    Generator,
+    Async,
 }
 
 #[derive(Clone)]
 pub struct Parameters {
     pub param: String,
+    /// Evaluated in the function's scope when a call leaves this out.
+    pub default: Option<Expression>,
+    /// `...param`, only allowed last.
+    pub variadic: bool,
     pub rest: Option<Box<Parameters>>,
 }
 
 #[derive(Clone)]
 pub struct Arguments {
     pub expr: Expression,
+    pub spread: bool,
     pub rest: Option<Box<Arguments>>,
 }
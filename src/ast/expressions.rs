@@ -1,14 +1,27 @@
 use super::*;
 
+/// An expression and the line it starts on, used to report runtime errors.
 #[derive(Clone)]
-pub struct Expression(pub Assignment);
+pub struct Expression {
+    pub expr: Assignment,
+    pub line: usize,
+}
 
 #[derive(Clone)]
 pub enum Assignment {
-    Assign(Call, Box<Assignment>),
+    Assign(Call, AssignOp, Box<Assignment>),
+    /// `(a, b) = value`, unpacks a list into the targets.
+    Destructure(Vec<Call>, Tuple),
+    Comma(Box<Assignment>, Box<Assignment>),
+    Ternary(Box<Assignment>, Box<Assignment>, Box<Assignment>),
+    Coalesce(LogicOr, Box<Assignment>),
     LogicOr(LogicOr),
 }
 
+/// `return a, b`, where several values make a list.
+#[derive(Clone)]
+pub struct Tuple(pub Vec<Expression>);
+
 #[derive(Clone)]
 pub struct LogicOr {
     pub and: LogicAnd,
@@ -48,6 +61,11 @@ pub struct Factor {
 #[derive(Clone)]
 pub enum Unary {
     Un(UnaryOp, Box<Unary>),
+    PreIncr(IncrOp, Call),
+    PostIncr(Call, IncrOp),
+    /// `-2 ** 2` is `-(2 ** 2)`.
+    Pow(Box<Unary>, Box<Unary>),
+    Await(Token, Box<Unary>),
     Call(Call),
 }
 
@@ -60,21 +78,47 @@ pub struct Call {
 #[derive(Clone)]
 pub enum Calling {
     FuncCall(Option<Arguments>),
+    Index(Box<Expression>),
     Mthd(String),
+    /// `?.name`, ends the whole chain with nil when called on nil.
+    OptMthd(String),
 }
 
 #[derive(Clone)]
 pub enum Primary {
     Number(f64),
     String(String),
+    Interpolation(Vec<StrPart>),
     Boolean(bool),
     Identifier(String),
+    List(Option<Box<Arguments>>),
+    Map(Vec<(Expression, Expression)>),
+    Lambda(Box<Function>),
     ParenExpr(Box<Expression>),
     SuperId(String),
     This,
     Nil,
 }
 
+#[derive(Clone)]
+pub enum StrPart {
+    Str(String),
+    Expr(Expression),
+}
+
+#[derive(Clone)]
+pub enum AssignOp {
+    Equal,
+    Term(TermOp),
+    Factor(FactorOp),
+}
+
+#[derive(Clone)]
+pub enum IncrOp {
+    Incr,
+    Decr,
+}
+
 #[derive(Clone)]
 pub enum UnaryOp {
     Minus,
@@ -105,4 +149,8 @@ pub enum TermOp {
 pub enum FactorOp {
     Mul,
     Div,
+    /// `%`, takes the sign of the divisor so that it agrees with `//`.
+    Mod,
+    /// `//`, divides and rounds down.
+    IntDiv,
 }
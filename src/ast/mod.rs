@@ -2,6 +2,8 @@ pub mod declarations;
 pub mod expressions;
 pub mod statements;
 
+use crate::token::Token;
+
 pub use declarations::*;
 pub use expressions::*;
 pub use statements::*;
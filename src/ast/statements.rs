@@ -1,16 +1,30 @@
 use super::*;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone)]
 pub enum Statement {
+    BrkStmt(BrkStmt),
+    CntStmt(CntStmt),
     ExprStmt(ExprStmt),
     ForStmt(ForStmt),
+    ForInStmt(ForInStmt),
     IfStmt(IfStmt),
+    MatchStmt(MatchStmt),
     PrntStmt(PrntStmt),
     RtrnStmt(RtrnStmt),
+    ThrwStmt(ThrwStmt),
+    TryStmt(TryStmt),
     WhileStmt(WhileStmt),
+    YieldStmt(YieldStmt),
     Block(Block),
 }
 
+#[derive(Clone)]
+pub struct BrkStmt(pub Token);
+
+#[derive(Clone)]
+pub struct CntStmt(pub Token);
+
 #[derive(Clone)]
 pub struct ExprStmt(pub Expression);
 
@@ -22,6 +36,15 @@ pub struct ForStmt {
     pub body: Box<Statement>,
 }
 
+/// `lo..hi` counts from `lo` up to but not including `hi`.
+#[derive(Clone)]
+pub struct ForInStmt {
+    pub binding: Binding,
+    pub iterable: Expression,
+    pub end: Option<Expression>,
+    pub body: Box<Statement>,
+}
+
 #[derive(Clone)]
 pub enum ForDec {
     VarDecl(VarDecl),
@@ -36,11 +59,50 @@ pub struct IfStmt {
     pub else_stmt: Option<Box<Statement>>,
 }
 
+#[derive(Clone)]
+pub struct MatchStmt {
+    pub subject: Expression,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Clone)]
+pub struct MatchArm {
+    pub tok: Token,
+    pub patterns: Option<Vec<Pattern>>,
+    pub body: Box<Statement>,
+}
+
+#[derive(Clone)]
+pub enum Pattern {
+    Value(Primary),
+    /// `lo..hi`, matches numbers from `lo` up to but not including `hi`.
+    Range(f64, f64),
+    /// Matches instances of the class or a subclass, or enum variants by name.
+    Class(String, Vec<String>),
+}
+
 #[derive(Clone)]
 pub struct PrntStmt(pub Expression);
 
 #[derive(Clone)]
-pub struct RtrnStmt(pub Option<Expression>);
+pub struct RtrnStmt(pub Option<Tuple>);
+
+#[derive(Clone)]
+pub struct YieldStmt {
+    pub tok: Token,
+    pub expr: Option<Expression>,
+}
+
+#[derive(Clone)]
+pub struct ThrwStmt(pub Expression);
+
+/// At least one of `catch` and `finally` is always present.
+#[derive(Clone)]
+pub struct TryStmt {
+    pub body: Block,
+    pub catch: Option<(String, Block)>,
+    pub finally: Option<Block>,
+}
 
 #[derive(Clone)]
 pub struct WhileStmt {
@@ -5,7 +5,10 @@ impl Display for Declaration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Declaration::ClassDecl(class_decl) => class_decl.fmt(f),
+            Declaration::EnumDecl(enum_decl) => enum_decl.fmt(f),
             Declaration::FunDecl(fun_decl) => fun_decl.fmt(f),
+            Declaration::ImportDecl(import_decl) => import_decl.fmt(f),
+            Declaration::RecordDecl(record_decl) => record_decl.fmt(f),
             Declaration::VarDecl(var_decl) => var_decl.fmt(f),
             Declaration::Statement(statement) => statement.fmt(f),
         }
@@ -19,34 +22,94 @@ impl Display for ClassDecl {
             write!(f, " < {supa}")?;
         }
         writeln!(f, "{{")?;
+        for field in &self.fields {
+            write!(f, "\tstatic {}", field.name)?;
+            if let Some(e) = &field.expr {
+                write!(f, " = {e}")?;
+            }
+            writeln!(f, ";")?;
+        }
+        for func in &self.statics {
+            write_decorators(f, func, "\t")?;
+            writeln!(f, "\tstatic {}", indent(func.to_string()))?;
+        }
+        for func in &self.getters {
+            writeln!(f, "\t{} {}", func.name, indent(func.body.to_string()))?;
+        }
+        for func in &self.setters {
+            writeln!(f, "\tset {}", indent(func.to_string()))?;
+        }
         for func in &self.functions {
-            writeln!(f, "\t{}", indent(func.to_string()))?;
+            write_decorators(f, func, "\t")?;
+            write!(f, "\t")?;
+            if func.kind == FunKind::Async {
+                write!(f, "async ")?;
+            }
+            writeln!(f, "{}", indent(func.to_string()))?;
         }
         writeln!(f, "}}")
     }
 }
 
+impl Display for EnumDecl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let variants = self
+            .variants
+            .iter()
+            .map(|(name, fields)| match fields.is_empty() {
+                true => name.clone(),
+                false => format!("{name}({})", fields.join(", ")),
+            })
+            .collect::<Vec<_>>();
+        writeln!(f, "enum {} {{ {} }}", self.name, variants.join(", "))
+    }
+}
+
 impl Display for FunDecl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "fun {}", self.0)
+        write_decorators(f, &self.0, "")?;
+        match self.0.kind {
+            FunKind::Async => write!(f, "async fun {}", self.0),
+            _ => write!(f, "fun {}", self.0),
+        }
+    }
+}
+
+impl Display for ImportDecl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.names {
+            Imports::As(name) => writeln!(f, "import \"{}\" as {name};", self.path),
+            Imports::Names(names) => {
+                writeln!(f, "from \"{}\" import {};", self.path, names.join(", "))
+            }
+        }
+    }
+}
+
+impl Display for RecordDecl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "record {}({});", self.name, self.fields.join(", "))
     }
 }
 
 impl Display for VarDecl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "var {}", self.name)?;
-        if let Some(e) = &self.expr {
-            write!(f, " = {e}")?;
+        match self {
+            VarDecl::Name(name, None) => writeln!(f, "var {name};"),
+            VarDecl::Name(name, Some(e)) => writeln!(f, "var {name} = {e};"),
+            VarDecl::Tuple(names, values) => writeln!(f, "var ({}) = {values};", names.join(", ")),
         }
-        writeln!(f, ";")
     }
 }
 
 impl Display for Function {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
+        if self.kind == FunKind::Generator {
+            write!(f, "*")?;
+        }
+        writeln!(
             f,
-            "{}({}) {}\n",
+            "{}({}) {}",
             self.name,
             self.params.as_ref().map_or("".into(), |x| x.to_string()),
             self.body
@@ -56,10 +119,27 @@ impl Display for Function {
 
 impl Display for Parameters {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.variadic {
+            write!(f, "...")?;
+        }
         write!(f, "{}", self.param)?;
+        if let Some(default) = &self.default {
+            write!(f, " = {default}")?;
+        }
         match &self.rest {
             Some(v) => write!(f, ", {v}"),
             None => Ok(()),
         }
     }
 }
+
+fn write_decorators(
+    f: &mut std::fmt::Formatter<'_>,
+    func: &Function,
+    indent: &str,
+) -> std::fmt::Result {
+    for d in &func.decorators {
+        writeln!(f, "{indent}@{d}")?;
+    }
+    Ok(())
+}
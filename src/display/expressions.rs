@@ -3,14 +3,31 @@ use std::fmt::Display;
 
 impl Display for Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.0.fmt(f)
+        self.expr.fmt(f)
+    }
+}
+
+impl Display for Tuple {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let [value] = &self.0[..] {
+            return value.fmt(f);
+        }
+        let values: Vec<_> = self.0.iter().map(|v| v.to_string()).collect();
+        write!(f, "(tuple {})", values.join(" "))
     }
 }
 
 impl Display for Assignment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Assignment::Assign(call, assignment) => write!(f, "{call} = {assignment}"),
+            Assignment::Assign(call, op, assignment) => write!(f, "{call} {op} {assignment}"),
+            Assignment::Destructure(targets, value) => {
+                let targets = targets.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+                write!(f, "({}) = {value}", targets.join(", "))
+            }
+            Assignment::Comma(left, right) => write!(f, "(, {left} {right})"),
+            Assignment::Ternary(pred, then, els) => write!(f, "(? {pred} {then} {els})"),
+            Assignment::Coalesce(value, fallback) => write!(f, "(?? {value} {fallback})"),
             Assignment::LogicOr(logic_or) => write!(f, "{logic_or}"),
         }
     }
@@ -100,6 +117,8 @@ impl Display for FactorOp {
         match self {
             FactorOp::Mul => write!(f, "*"),
             FactorOp::Div => write!(f, "/"),
+            FactorOp::Mod => write!(f, "%"),
+            FactorOp::IntDiv => write!(f, "//"),
         }
     }
 }
@@ -114,6 +133,25 @@ impl Display for Factor {
     }
 }
 
+impl Display for AssignOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssignOp::Equal => write!(f, "="),
+            AssignOp::Term(op) => write!(f, "{op}="),
+            AssignOp::Factor(op) => write!(f, "{op}="),
+        }
+    }
+}
+
+impl Display for IncrOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IncrOp::Incr => write!(f, "++"),
+            IncrOp::Decr => write!(f, "--"),
+        }
+    }
+}
+
 impl Display for UnaryOp {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -127,6 +165,10 @@ impl Display for Unary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Unary::Un(unary_op, unary) => write!(f, "({} {})", unary_op, unary),
+            Unary::PreIncr(op, call) => write!(f, "({op} {call})"),
+            Unary::PostIncr(call, op) => write!(f, "({call} {op})"),
+            Unary::Pow(base, exp) => write!(f, "(** {base} {exp})"),
+            Unary::Await(_, value) => write!(f, "(await {value})"),
             Unary::Call(call) => call.fmt(f),
         }
     }
@@ -149,7 +191,9 @@ impl Display for Calling {
                 Some(a) => write!(f, "({a})"),
                 None => write!(f, "()"),
             },
+            Calling::Index(i) => write!(f, "[{i}]"),
             Calling::Mthd(c) => write!(f, ".{c}"),
+            Calling::OptMthd(c) => write!(f, "?.{c}"),
         }
     }
 }
@@ -158,11 +202,39 @@ impl Display for Primary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let v = match self {
             Primary::Number(n) => format_float(n),
-            Primary::String(s) => format!("{s}"),
+            Primary::String(s) => s.clone(),
+            Primary::Interpolation(parts) => parts
+                .iter()
+                .map(|p| match p {
+                    StrPart::Str(s) => s.clone(),
+                    StrPart::Expr(e) => format!("${{{e}}}"),
+                })
+                .collect(),
             Primary::Boolean(v) => v.to_string(),
             Primary::Nil => "nil".into(),
+            Primary::Map(entries) => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|(k, v)| format!("{k}: {v}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Primary::Lambda(func) => format!(
+                "{} ({}) {}",
+                match func.kind {
+                    FunKind::Plain => "fun",
+                    FunKind::Generator => "fun*",
+                    FunKind::Async => "async fun",
+                },
+                func.params.as_ref().map_or("".into(), |x| x.to_string()),
+                func.body
+            ),
             Primary::ParenExpr(expression) => format!("(group {expression})"),
             Primary::Identifier(id) => id.clone(),
+            Primary::List(elems) => {
+                format!("[{}]", elems.as_ref().map_or("".into(), |x| x.to_string()))
+            }
             Primary::This => "this".into(),
             Primary::SuperId(id) => format!("super.{id}"),
         };
@@ -180,6 +252,9 @@ fn format_float(value: &f64) -> String {
 
 impl Display for Arguments {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.spread {
+            write!(f, "...")?;
+        }
         write!(f, "{}", self.expr)?;
         match &self.rest {
             Some(v) => write!(f, ", {v}"),
@@ -4,17 +4,36 @@ use std::fmt::Display;
 impl Display for Statement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Statement::BrkStmt(brk_stmt) => brk_stmt.fmt(f),
+            Statement::CntStmt(cnt_stmt) => cnt_stmt.fmt(f),
             Statement::ExprStmt(expr_stmt) => expr_stmt.fmt(f),
             Statement::ForStmt(for_stmt) => for_stmt.fmt(f),
+            Statement::ForInStmt(for_in_stmt) => for_in_stmt.fmt(f),
             Statement::IfStmt(if_stmt) => if_stmt.fmt(f),
+            Statement::MatchStmt(match_stmt) => match_stmt.fmt(f),
             Statement::PrntStmt(prnt_stmt) => prnt_stmt.fmt(f),
             Statement::RtrnStmt(rtrn_stmt) => rtrn_stmt.fmt(f),
+            Statement::ThrwStmt(thrw_stmt) => thrw_stmt.fmt(f),
+            Statement::TryStmt(try_stmt) => try_stmt.fmt(f),
             Statement::WhileStmt(while_stmt) => while_stmt.fmt(f),
+            Statement::YieldStmt(yield_stmt) => yield_stmt.fmt(f),
             Statement::Block(block) => block.fmt(f),
         }
     }
 }
 
+impl Display for BrkStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "break;")
+    }
+}
+
+impl Display for CntStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "continue;")
+    }
+}
+
 impl Display for ExprStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{};", self.0)
@@ -36,6 +55,55 @@ impl Display for IfStmt {
     }
 }
 
+impl Display for MatchStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arms = self
+            .arms
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        write!(f, "match ({}) {{\n\t{}\n}}", self.subject, indent(arms))
+    }
+}
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.patterns {
+            Some(patterns) => {
+                let patterns = patterns.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+                write!(f, "case {} => {}", patterns.join(", "), self.body)
+            }
+            None => write!(f, "default => {}", self.body),
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Value(value) => value.fmt(f),
+            Pattern::Range(lo, hi) => write!(f, "{lo}..{hi}"),
+            Pattern::Class(name, fields) => write!(f, "{name}({})", fields.join(", ")),
+        }
+    }
+}
+
+impl Display for ForInStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.binding {
+            Binding::Name(name) => write!(f, "for (var {name} in {}", self.iterable)?,
+            Binding::Tuple(names) => {
+                write!(f, "for (var ({}) in {}", names.join(", "), self.iterable)?
+            }
+        }
+        if let Some(end) = &self.end {
+            write!(f, "..{end}")?;
+        }
+        write!(f, ") \n\t{}", indent(self.body.to_string()))
+    }
+}
+
 impl Display for ForStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -70,6 +138,15 @@ impl Display for WhileStmt {
     }
 }
 
+impl Display for YieldStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.expr {
+            Some(e) => write!(f, "yield {e};"),
+            None => write!(f, "yield;"),
+        }
+    }
+}
+
 impl Display for RtrnStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -80,6 +157,25 @@ impl Display for RtrnStmt {
     }
 }
 
+impl Display for ThrwStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "throw {};", self.0)
+    }
+}
+
+impl Display for TryStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "try {}", self.body)?;
+        if let Some((name, block)) = &self.catch {
+            write!(f, " catch ({name}) {block}")?;
+        }
+        if let Some(block) = &self.finally {
+            write!(f, " finally {block}")?;
+        }
+        Ok(())
+    }
+}
+
 impl Display for PrntStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "print {};", self.0)
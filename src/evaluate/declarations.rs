@@ -1,108 +1,245 @@
 use super::*;
+use crate::native_function::arity_error;
+use environment::find_id;
 
-impl Eval for Declaration {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for Declaration {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         match self {
-            Declaration::ClassDecl(class_decl) => class_decl.evaluate(env),
-            Declaration::FunDecl(fun_decl) => fun_decl.evaluate(env),
-            Declaration::VarDecl(var_decl) => var_decl.evaluate(env),
-            Declaration::Statement(statement) => statement.evaluate(env),
+            Declaration::ClassDecl(class_decl) => class_decl.execute(env),
+            Declaration::EnumDecl(enum_decl) => enum_decl.execute(env),
+            Declaration::FunDecl(fun_decl) => fun_decl.execute(env),
+            Declaration::ImportDecl(import_decl) => import_decl.execute(env),
+            Declaration::RecordDecl(record_decl) => record_decl.execute(env),
+            Declaration::VarDecl(var_decl) => var_decl.execute(env),
+            Declaration::Statement(statement) => statement.execute(env),
         }
     }
 }
 
-impl Eval for ClassDecl {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for ClassDecl {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         let name = self.name.clone();
-
-        let cls = |_,
-                   name: &Vec<String>,
-                   funcs: &Block,
-                   env: Rc<RefCell<Env>>|
-         -> Result<Object, RuntimeError> {
-            let env = Env::new_box_it(Some(env));
-            for f in &funcs.0 {
-                f.evaluate(env.clone())?;
+        let superclass = match &self.super_class {
+            Some(s) => {
+                let value = find_id(s, Some(env.clone()))
+                    .and_then(|e| e.borrow().values.get(s).cloned())
+                    .ok_or(RuntimeError::new(format!("Undefined variable '{s}'.")))?;
+                match value {
+                    Object::Function(ExFn {
+                        class: Some(def), ..
+                    }) => Some(def),
+                    _ => return Err(RuntimeError::new("Superclass must be a class.".into())),
+                }
             }
+            None => None,
+        };
+        let def = Rc::new(ClassDef::new(superclass));
+
+        let instance_of = def.clone();
+        let cls = move |_, name: &Vec<String>, _: &Block, _| -> Result<Object, RuntimeError> {
             Ok(Object::Object(Class {
                 name: name[0].clone(),
-                env,
+                env: Env::new_box_it(None),
+                class: Some(instance_of.clone()),
             }))
         };
 
-        let mut body = vec![];
-        for f in &self.functions {
-            body.push(Declaration::FunDecl(FunDecl(f.clone())));
-        }
-
         env.borrow_mut().values.insert(
             name.clone(),
             Object::Function(ExFn {
-                fun: Arc::new(cls),
-                body: Block(body),
+                fun: Rc::new(cls),
+                class: Some(def.clone()),
+                body: Block(vec![]),
                 env: env.clone(),
                 name: name.clone(),
-                params: vec![name.into()],
+                params: vec![name],
             }),
         );
-        Ok(Object::Nil)
-    }
-}
 
-impl Eval for FunDecl {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
-        let func = self.0.evaluate(env.clone())?;
-        let name = func.get_function().unwrap().name.clone();
-        env.borrow_mut().values.insert(name, func);
-        Ok(Object::Nil)
+        // methods take the instance as their first argument
+        let getters = self.getters.iter().map(|f| (f, "get "));
+        let setters = self.setters.iter().map(|f| (f, "set "));
+        let functions = self.functions.iter().map(|f| (f, ""));
+        for (f, prefix) in getters.chain(setters).chain(functions) {
+            let method = FunDecl(f.clone()).decorated(env.clone(), true)?;
+            let name = format!("{prefix}{}", f.name);
+            def.methods.borrow_mut().values.insert(name, method);
+        }
+
+        for f in &self.statics {
+            let method = FunDecl(f.clone()).decorated(env.clone(), false)?;
+            def.statics
+                .borrow_mut()
+                .values
+                .insert(f.name.clone(), method);
+        }
+        for field in &self.fields {
+            let value = match &field.expr {
+                Some(e) => e.evaluate(env.clone())?,
+                None => Object::Nil,
+            };
+            def.fields
+                .borrow_mut()
+                .values
+                .insert(field.name.clone(), value);
+        }
+        Ok(Completion::Normal)
     }
 }
 
-impl Eval for VarDecl {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
-        let res = if let Some(e) = &self.expr {
-            e.evaluate(env.clone())?
-        } else {
-            Object::Nil
+impl Exec for EnumDecl {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        let decl = Enum {
+            name: self.name.clone(),
+            variants: Rc::new(self.variants.clone()),
         };
         env.borrow_mut()
             .values
-            .insert(self.name.clone(), res.clone());
-        Ok(res)
+            .insert(self.name.clone(), Object::Enum(decl));
+        Ok(Completion::Normal)
+    }
+}
+
+impl Exec for RecordDecl {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        let ctor = records::constructor(&self.name, self.fields.clone());
+        env.borrow_mut().values.insert(self.name.clone(), ctor);
+        Ok(Completion::Normal)
+    }
+}
+
+impl Exec for FunDecl {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        let func = self.decorated(env.clone(), false)?;
+        env.borrow_mut().values.insert(self.0.name.clone(), func);
+        Ok(Completion::Normal)
+    }
+}
+
+impl FunDecl {
+    fn decorated(&self, env: Rc<RefCell<Env>>, method: bool) -> Result<Object, RuntimeError> {
+        let decorators = self
+            .0
+            .decorators
+            .iter()
+            .map(|d| Ok((d.evaluate(env.clone())?, d.line)))
+            .collect::<Result<Vec<_>, RuntimeError>>()?;
+        let mut func = self.0.evaluate(env.clone())?;
+        if let (true, Object::Function(f)) = (method, &func) {
+            func = Object::Function(f.clone().into_method());
+        }
+        // like nested calls, the decorator closest to the function goes first
+        for (decorator, line) in decorators.into_iter().rev() {
+            let decorator = decorator.get_function().ok_or_else(|| {
+                RuntimeError::new("Decorators must be functions or classes.".into()).at(line)
+            })?;
+            func = decorator.call(vec![func]).map_err(|e| e.at(line))?;
+        }
+        Ok(func)
+    }
+}
+
+impl Exec for ImportDecl {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        let module = module::import(&self.path)?;
+        match &self.names {
+            Imports::As(name) => {
+                let module = Module {
+                    name: name.clone(),
+                    ..module
+                };
+                env.borrow_mut()
+                    .values
+                    .insert(name.clone(), Object::Module(module));
+            }
+            Imports::Names(names) => {
+                for name in names {
+                    let value = module.get(name)?;
+                    env.borrow_mut().values.insert(name.clone(), value);
+                }
+            }
+        }
+        Ok(Completion::Normal)
+    }
+}
+
+impl Exec for VarDecl {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        match self {
+            VarDecl::Name(name, expr) => {
+                let res = if let Some(e) = expr {
+                    e.evaluate(env.clone())?
+                } else {
+                    Object::Nil
+                };
+                env.borrow_mut().values.insert(name.clone(), res);
+            }
+            VarDecl::Tuple(names, values) => {
+                let values = list::unpack(values.evaluate(env.clone())?, names.len())?;
+                for (name, value) in names.iter().zip(values) {
+                    env.borrow_mut().values.insert(name.clone(), value);
+                }
+            }
+        }
+        Ok(Completion::Normal)
+    }
+}
+
+impl Binding {
+    pub fn bind(&self, value: Object, env: &Rc<RefCell<Env>>) -> Result<(), RuntimeError> {
+        match self {
+            Binding::Name(name) => {
+                env.borrow_mut().values.insert(name.clone(), value);
+            }
+            Binding::Tuple(names) => {
+                let values = list::unpack(value, names.len())?;
+                for (name, value) in names.iter().zip(values) {
+                    env.borrow_mut().values.insert(name.clone(), value);
+                }
+            }
+        }
+        Ok(())
     }
 }
 
 impl Eval for Function {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
-        let params = self
-            .params
-            .as_ref()
-            .map_or(Ok(vec![]), |x| x.evaluate(env.clone()))?;
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+        let spec = self.params.clone();
+        let params = spec.as_ref().map_or(vec![], |x| {
+            x.in_order().iter().map(|p| p.param.clone()).collect()
+        });
 
+        let (name, kind) = (self.name.clone(), self.kind);
         let res =
-            |passed: Vec<Object>, params: &Vec<String>, body: &Block, env: Rc<RefCell<Env>>| {
-                if passed.len() != params.len() {
-                    return Err(RuntimeError {
-                        err: format!(
-                            "Expect {} arguments but got {}.",
-                            params.len(),
-                            passed.len()
-                        ),
-                    });
-                }
-                let params = params.clone();
+            move |passed: Vec<Object>, _: &Vec<String>, body: &Block, env: Rc<RefCell<Env>>| {
+                let params = spec.as_ref().map_or(vec![], |x| x.in_order());
+                Parameters::check_arity(&params, passed.len())?;
 
                 let env = Env::new_box_it(Some(env));
 
-                for (name, value) in params.into_iter().zip(passed) {
-                    env.borrow_mut().values.insert(name, value);
+                let mut passed = passed.into_iter();
+                for p in params {
+                    let value = if p.variadic {
+                        list::new_list(passed.by_ref().collect())
+                    } else if let Some(v) = passed.next() {
+                        v
+                    } else if let Some(default) = &p.default {
+                        default.evaluate(env.clone())?
+                    } else {
+                        Object::Nil
+                    };
+                    env.borrow_mut().values.insert(p.param.clone(), value);
                 }
 
-                for expr in &body.0 {
-                    let val = expr.evaluate(env.clone())?;
-                    match val {
-                        Object::Return(v) => return Ok(v.as_ref().clone()),
-                        _ => {}
+                match kind {
+                    FunKind::Generator => return Ok(generator::new_generator(&name, body, env)),
+                    FunKind::Async => return Ok(promise::run_async(&name, body, env)),
+                    FunKind::Plain => {}
+                }
+
+                for d in &body.0 {
+                    if let Completion::Return(v) = d.execute(env.clone())? {
+                        return Ok(v);
                     }
                 }
 
@@ -111,7 +248,8 @@ impl Eval for Function {
 
         Ok(Object::Function(ExFn {
             name: self.name.clone(),
-            fun: Arc::new(res),
+            fun: Rc::new(res),
+            class: None,
             body: self.body.clone(),
             env: env.clone(),
             params,
@@ -120,10 +258,29 @@ impl Eval for Function {
 }
 
 impl Parameters {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Vec<String>, RuntimeError> {
-        let mut res = self.rest.as_ref().map_or(Ok(vec![]), |x| x.evaluate(env))?;
-        let this = self.param.clone();
-        res.push(this);
-        Ok(res)
+    fn in_order(&self) -> Vec<&Parameters> {
+        let mut res = self.rest.as_ref().map_or(vec![], |x| x.in_order());
+        res.push(self);
+        res
+    }
+
+    fn check_arity(params: &[&Parameters], got: usize) -> Result<(), RuntimeError> {
+        let min = params
+            .iter()
+            .filter(|p| p.default.is_none() && !p.variadic)
+            .count();
+        let expect = if params.last().is_some_and(|p| p.variadic) {
+            if got >= min {
+                return Ok(());
+            }
+            format!("at least {min}")
+        } else if (min..=params.len()).contains(&got) {
+            return Ok(());
+        } else if min == params.len() {
+            min.to_string()
+        } else {
+            format!("{min} to {}", params.len())
+        };
+        Err(arity_error(expect, got))
     }
 }
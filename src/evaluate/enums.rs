@@ -0,0 +1,67 @@
+use super::*;
+use crate::native_function::{arity, native};
+
+#[derive(Clone)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Rc<Vec<VariantDecl>>,
+}
+
+#[derive(Clone)]
+pub struct Variant {
+    pub of: Enum,
+    pub index: usize,
+    pub values: Vec<Object>,
+}
+
+impl Enum {
+    pub fn position(&self, name: &str) -> Result<usize, RuntimeError> {
+        self.variants
+            .iter()
+            .position(|(v, _)| v == name)
+            .ok_or(RuntimeError::new(format!(
+                "Unknown variant '{name}' of enum '{}'.",
+                self.name
+            )))
+    }
+
+    /// Variants with fields are functions that build them.
+    pub fn get(&self, name: &str) -> Result<Object, RuntimeError> {
+        let index = self.position(name)?;
+        let fields = &self.variants[index].1;
+        if fields.is_empty() {
+            return Ok(Object::Variant(Variant {
+                of: self.clone(),
+                index,
+                values: vec![],
+            }));
+        }
+
+        let of = self.clone();
+        let params: Vec<_> = fields.iter().map(String::as_str).collect();
+        let len = params.len();
+        Ok(native(name, &params, move |passed| {
+            arity(len, passed.len())?;
+            Ok(Object::Variant(Variant {
+                of: of.clone(),
+                index,
+                values: passed,
+            }))
+        }))
+    }
+}
+
+impl Variant {
+    pub fn name(&self) -> &str {
+        &self.of.variants[self.index].0
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.of.variants[self.index].1
+    }
+
+    pub fn get(&self, field: &str) -> Option<Object> {
+        let i = self.fields().iter().position(|f| f == field)?;
+        Some(self.values[i].clone())
+    }
+}
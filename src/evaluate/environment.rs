@@ -21,7 +21,7 @@ impl Env {
     }
 }
 
-pub fn find_id<'a>(id: &String, env: Option<Rc<RefCell<Env>>>) -> Option<Rc<RefCell<Env>>> {
+pub fn find_id(id: &String, env: Option<Rc<RefCell<Env>>>) -> Option<Rc<RefCell<Env>>> {
     let mut current_env = env;
     while let Some(env) = current_env {
         if env.borrow().values.contains_key(id) {
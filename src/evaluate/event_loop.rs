@@ -0,0 +1,136 @@
+use std::{
+    collections::VecDeque,
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::*;
+
+pub type Task = Rc<dyn Fn() -> Result<(), RuntimeError>>;
+
+pub type Microtask = Box<dyn FnOnce() -> Result<(), RuntimeError>>;
+
+/// Kept per thread, as there is one interpreter per thread.
+struct EventLoop {
+    timers: Vec<Timer>,
+    microtasks: VecDeque<Microtask>,
+    /// Orders timers due at the same time.
+    seq: u64,
+    started: Instant,
+    /// Set when timers fire without waiting, so runs are deterministic.
+    virtual_now: Option<f64>,
+}
+
+struct Timer {
+    at: f64,
+    seq: u64,
+    id: u64,
+    task: Task,
+    interval: Option<f64>,
+}
+
+thread_local! {
+    static LOOP: RefCell<EventLoop> = RefCell::new(EventLoop {
+        timers: vec![],
+        microtasks: VecDeque::new(),
+        seq: 0,
+        started: Instant::now(),
+        virtual_now: None,
+    });
+}
+
+pub fn use_virtual_clock() {
+    LOOP.with_borrow_mut(|l| l.virtual_now = Some(0.0));
+}
+
+pub fn is_virtual() -> bool {
+    LOOP.with_borrow(|l| l.virtual_now.is_some())
+}
+
+pub fn now() -> f64 {
+    LOOP.with_borrow(|l| l.now())
+}
+
+/// Gives the id to clear the timer with.
+pub fn set_timer(task: Task, ms: f64, repeat: bool) -> u64 {
+    LOOP.with_borrow_mut(|l| {
+        let ms = ms.max(0.0);
+        let at = l.now() + ms;
+        let interval = repeat.then_some(ms);
+        let id = l.seq + 1;
+        l.schedule(Timer {
+            at,
+            seq: 0,
+            id,
+            task,
+            interval,
+        });
+        id
+    })
+}
+
+pub fn clear_timer(id: u64) {
+    LOOP.with_borrow_mut(|l| l.timers.retain(|t| t.id != id));
+}
+
+pub fn queue_microtask(task: Microtask) {
+    LOOP.with_borrow_mut(|l| l.microtasks.push_back(task));
+}
+
+/// Microtasks first, then the timer due next.
+pub fn run() -> Result<(), RuntimeError> {
+    loop {
+        while let Some(task) = LOOP.with_borrow_mut(|l| l.microtasks.pop_front()) {
+            task()?;
+        }
+        promise::check_unhandled()?;
+
+        let Some(timer) = LOOP.with_borrow_mut(|l| l.next_timer()) else {
+            return Ok(());
+        };
+        let wait = LOOP.with_borrow_mut(|l| match &mut l.virtual_now {
+            Some(now) => {
+                *now = now.max(timer.at);
+                0.0
+            }
+            None => timer.at - l.now(),
+        });
+        if wait > 0.0 {
+            thread::sleep(Duration::from_secs_f64(wait / 1000.0));
+        }
+
+        let task = timer.task.clone();
+        if let Some(interval) = timer.interval {
+            // scheduled again before it runs, so the callback can clear it
+            LOOP.with_borrow_mut(|l| {
+                l.schedule(Timer {
+                    at: timer.at + interval,
+                    ..timer
+                })
+            });
+        }
+        task()?;
+    }
+}
+
+impl EventLoop {
+    fn now(&self) -> f64 {
+        self.virtual_now
+            .unwrap_or_else(|| self.started.elapsed().as_secs_f64() * 1000.0)
+    }
+
+    fn schedule(&mut self, timer: Timer) {
+        self.seq += 1;
+        let seq = self.seq;
+        self.timers.push(Timer { seq, ..timer });
+    }
+
+    fn next_timer(&mut self) -> Option<Timer> {
+        let (i, _) = self
+            .timers
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.at.total_cmp(&b.at).then(a.seq.cmp(&b.seq)))?;
+        Some(self.timers.swap_remove(i))
+    }
+}
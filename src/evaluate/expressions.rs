@@ -3,45 +3,69 @@ use environment::find_id;
 use super::*;
 
 impl Eval for Expression {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
-        self.0.evaluate(env)
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+        self.expr.evaluate(env).map_err(|e| e.at(self.line))
+    }
+}
+
+impl Tuple {
+    pub fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+        if let [value] = &self.0[..] {
+            return value.evaluate(env);
+        }
+        let values = self
+            .0
+            .iter()
+            .map(|v| v.evaluate(env.clone()))
+            .collect::<Result<_, _>>()?;
+        Ok(list::new_list(values))
     }
 }
 
 impl Eval for Assignment {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         match self {
-            Assignment::Assign(call, assignment) => match &call.prime {
-                Primary::Identifier(id) => {
-                    let res = assignment.evaluate(env.clone())?;
-                    let ev = find_id(id, Some(env.clone())).ok_or(RuntimeError {
-                        err: format!("unbound variable {id}"),
-                    })?;
-                    let mut v = ev.borrow().values.get(id).unwrap().clone();
-                    for c in &call.rest[..call.rest.len() - 1] {
-                        v = c.evaluate(v, env.clone())?;
+            Assignment::Assign(call, op, assignment) => {
+                let place = Place::of(call, env.clone())?;
+                let res = match op {
+                    AssignOp::Equal => assignment.evaluate(env)?,
+                    AssignOp::Term(op) => {
+                        let left = place.get(env.clone())?;
+                        op.evaluate(left, assignment.evaluate(env)?)?
                     }
-                    if call.rest.len() > 0 {
-                        match &call.rest.last().unwrap() {
-                            Calling::FuncCall(_) => Err(RuntimeError {
-                                err: "cannot assign to function calls".into(),
-                            }),
-                            Calling::Mthd(id) => {
-                                let cls = v.get_object().ok_or(RuntimeError {
-                                    err: "method only allowed for objects.".into(),
-                                })?;
-                                cls.env.borrow_mut().values.insert(id.clone(), res.clone());
-                                Ok(res)
-                            }
-                        }
-                    } else {
-                        ev.borrow_mut().values.insert(id.clone(), res.clone());
-                        Ok(res)
+                    AssignOp::Factor(op) => {
+                        let left = place.get(env.clone())?;
+                        op.evaluate(left, assignment.evaluate(env)?)?
                     }
+                };
+                place.set(res)
+            }
+            Assignment::Destructure(targets, value) => {
+                let places = targets
+                    .iter()
+                    .map(|t| Place::of(t, env.clone()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let res = value.evaluate(env)?;
+                let values = list::unpack(res.clone(), places.len())?;
+                for (place, value) in places.iter().zip(values) {
+                    place.set(value)?;
                 }
-                _ => Err(RuntimeError {
-                    err: "Expect identifier".into(),
-                }),
+                Ok(res)
+            }
+            Assignment::Comma(left, right) => {
+                left.evaluate(env.clone())?;
+                right.evaluate(env)
+            }
+            Assignment::Ternary(pred, then, els) => {
+                if statements::get_do_or_not(pred.evaluate(env.clone())?) {
+                    then.evaluate(env)
+                } else {
+                    els.evaluate(env)
+                }
+            }
+            Assignment::Coalesce(value, fallback) => match value.evaluate(env.clone())? {
+                Object::Nil => fallback.evaluate(env),
+                v => Ok(v),
             },
             Assignment::LogicOr(logic_or) => logic_or.evaluate(env),
         }
@@ -49,7 +73,7 @@ impl Eval for Assignment {
 }
 
 impl Eval for LogicOr {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         let right = self.and.evaluate(env.clone())?;
         if let Some(rest) = &self.rest {
             let left = rest.evaluate(env)?;
@@ -77,7 +101,7 @@ impl LogicOr {
 }
 
 impl Eval for LogicAnd {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         let right = self.eq.evaluate(env.clone())?;
         if let Some(rest) = &self.rest {
             let left = rest.evaluate(env)?;
@@ -105,7 +129,7 @@ impl LogicAnd {
 }
 
 impl Eval for Equality {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         if let Some((op, next)) = &self.rest {
             let right = self.comparision.evaluate(env.clone())?;
             let left = next.evaluate(env)?;
@@ -117,7 +141,7 @@ impl Eval for Equality {
 }
 
 impl Eval for Comparision {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         if let Some((op, next)) = &self.rest {
             let right = self.term.evaluate(env.clone())?;
             let left = next.evaluate(env)?;
@@ -129,7 +153,7 @@ impl Eval for Comparision {
 }
 
 impl Eval for Term {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         if let Some((op, next)) = &self.rest {
             let right = self.factor.evaluate(env.clone())?;
             let left = next.evaluate(env)?;
@@ -141,7 +165,7 @@ impl Eval for Term {
 }
 
 impl Eval for Factor {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         if let Some((op, next)) = &self.rest {
             let right = self.unary.evaluate(env.clone())?;
             let left = next.evaluate(env)?;
@@ -153,73 +177,243 @@ impl Eval for Factor {
 }
 
 impl Eval for Unary {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         match self {
             Unary::Un(op, unary) => {
                 let un = unary.evaluate(env)?;
                 op.evaluate(un)
             }
+            Unary::PreIncr(op, call) => Ok(op.apply(call, env)?.1),
+            Unary::PostIncr(call, op) => Ok(op.apply(call, env)?.0),
+            Unary::Pow(base, exp) => {
+                let base = base.evaluate(env.clone())?;
+                let exp = exp.evaluate(env)?;
+                operations::pow(base, exp)
+            }
+            Unary::Await(_, value) => generator::await_value(|| value.evaluate(env)),
             Unary::Call(call) => call.evaluate(env),
         }
     }
 }
 
+impl IncrOp {
+    fn apply(&self, call: &Call, env: Rc<RefCell<Env>>) -> Result<(Object, Object), RuntimeError> {
+        let place = Place::of(call, env.clone())?;
+        let old = place.get(env)?;
+        let n = old
+            .get_number()
+            .ok_or(RuntimeError::new("Operand must be a number.".into()))?;
+        let new = match self {
+            IncrOp::Incr => Object::Number(n + 1.0),
+            IncrOp::Decr => Object::Number(n - 1.0),
+        };
+        Ok((old, place.set(new)?))
+    }
+}
+
+/// Evaluated once, so `a[f()] += 1` reads and writes the same slot.
+enum Place {
+    Var(String, Rc<RefCell<Env>>),
+    Index(Object, Object),
+    Field(Object, String),
+}
+
+impl Place {
+    fn of(call: &Call, env: Rc<RefCell<Env>>) -> Result<Self, RuntimeError> {
+        if call.rest.iter().any(|c| matches!(c, Calling::OptMthd(_))) {
+            return Err(RuntimeError::new(
+                "Can't assign to an optional chain.".into(),
+            ));
+        }
+        let Some((last, init)) = call.rest.split_last() else {
+            let Primary::Identifier(id) = &call.prime else {
+                return Err(RuntimeError::new("Expect identifier".into()));
+            };
+            let ev = find_id(id, Some(env))
+                .ok_or(RuntimeError::new(format!("unbound variable {id}")))?;
+            return Ok(Place::Var(id.clone(), ev));
+        };
+        let mut v = call.prime.evaluate(env.clone())?;
+        for c in init {
+            v = c.step(v, env.clone())?;
+        }
+        match last {
+            Calling::FuncCall(_) => {
+                Err(RuntimeError::new("cannot assign to function calls".into()))
+            }
+            Calling::Index(i) => Ok(Place::Index(v, i.evaluate(env)?)),
+            Calling::Mthd(id) | Calling::OptMthd(id) => Ok(Place::Field(v, id.clone())),
+        }
+    }
+
+    fn get(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+        generator::replayed(|| match self {
+            Place::Var(id, ev) => Ok(ev.borrow().values.get(id).cloned().unwrap_or(Object::Nil)),
+            Place::Index(v, i) => operations::index(v, i),
+            Place::Field(v, id) => Calling::Mthd(id.clone()).step(v.clone(), env),
+        })
+    }
+
+    fn set(&self, res: Object) -> Result<Object, RuntimeError> {
+        match self {
+            Place::Var(id, ev) => {
+                ev.borrow_mut().values.insert(id.clone(), res.clone());
+                Ok(res)
+            }
+            Place::Index(v, i) => operations::set_index(v, i, res),
+            Place::Field(Object::Record(_), _) => Err(RuntimeError::new(
+                "Can't assign to record fields, use 'with' to copy with changes.".into(),
+            )),
+            Place::Field(
+                Object::Function(ExFn {
+                    class: Some(def), ..
+                }),
+                id,
+            ) => def
+                .set(id, res)
+                .ok_or_else(|| def.missing(id, "Only instances have fields.")),
+            Place::Field(v, id) => {
+                let cls = v
+                    .get_object()
+                    .ok_or(RuntimeError::new("Only instances have fields.".into()))?;
+                if let Some(setter) = cls.accessor("set ", id) {
+                    setter.call(vec![res.clone()])?;
+                    return Ok(res);
+                }
+                if cls.accessor("get ", id).is_some() {
+                    return Err(RuntimeError::new(format!(
+                        "Can't assign to getter-only property '{id}'."
+                    )));
+                }
+                cls.env.borrow_mut().values.insert(id.clone(), res.clone());
+                Ok(res)
+            }
+        }
+    }
+}
+
 impl Eval for Call {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         let mut pr = self.prime.evaluate(env.clone())?;
         for r in &self.rest {
-            pr = r.evaluate(pr, env.clone())?;
+            if let (Calling::OptMthd(_), Object::Nil) = (r, &pr) {
+                return Ok(Object::Nil);
+            }
+            pr = r.step(pr, env.clone())?;
         }
         Ok(pr)
     }
 }
 
 impl Calling {
+    fn step(&self, exp: Object, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+        generator::replayed(|| self.evaluate(exp, env))
+    }
+
     fn evaluate(&self, exp: Object, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         match &self {
             Calling::FuncCall(arguments) => {
-                let func = exp.get_function().ok_or(RuntimeError {
-                    err: "Can only call functions and classes.".into(),
-                })?;
+                let func = exp.get_function().ok_or(RuntimeError::new(
+                    "Can only call functions and classes.".into(),
+                ))?;
                 let args = arguments
                     .as_ref()
                     .map_or(Ok(vec![]), |x| x.evaluate(env.clone()))?;
-                let res = func.fun.as_ref()(args, &func.params, &func.body, func.env.clone())?;
-                Ok(res)
+                func.call(args)
+            }
+            Calling::Index(i) => {
+                let i = i.evaluate(env)?;
+                operations::index(&exp, &i)
             }
-            Calling::Mthd(call) => {
-                let obj = exp.get_object().ok_or(RuntimeError {
-                    err: "Can only call methods on objects.".into(),
-                })?;
-                let res = find_id(call, Some(obj.env.clone())).ok_or(RuntimeError {
-                    err: "unbound variable".into(),
-                })?;
-                let rborrowed = res.borrow();
-                Ok(rborrowed.values.get(call).unwrap().clone())
+            Calling::Mthd(call) | Calling::OptMthd(call) => {
+                match &exp {
+                    Object::List(list) => return list::method(list, call),
+                    Object::Map(map) => return map::method(map, call),
+                    Object::Module(module) => return module.get(call),
+                    Object::Generator(g) => return generator::method(g, call),
+                    Object::Promise(p) => return promise::method(p, call),
+                    Object::Enum(decl) => return decl.get(call),
+                    Object::Record(record) => return record.get(call),
+                    Object::Function(ExFn {
+                        class: Some(def), ..
+                    }) => {
+                        return def
+                            .get(call)
+                            .ok_or_else(|| def.missing(call, "Only instances have properties."))
+                    }
+                    Object::Variant(v) => {
+                        return v
+                            .get(call)
+                            .ok_or(RuntimeError::new(format!("Undefined property '{call}'.")))
+                    }
+                    _ => {}
+                }
+                let obj = exp.get_object().ok_or(RuntimeError::new(
+                    "Can only call methods on objects.".into(),
+                ))?;
+                if let Some(getter) = obj.accessor("get ", call) {
+                    return getter.call(vec![]);
+                }
+                let own = obj.env.borrow().values.get(call).cloned();
+                own.or_else(|| Some(Object::Function(obj.bound(call)?)))
+                    .or_else(|| obj.class.as_ref()?.get(call))
+                    .ok_or(RuntimeError::new(format!("Undefined property '{call}'.")))
             }
         }
     }
 }
 
 impl Eval for Primary {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
         match self {
             Primary::ParenExpr(expression) => expression.evaluate(env),
+            Primary::Lambda(func) => func.evaluate(env),
             Primary::Number(n) => Ok(Object::Number(*n)),
             Primary::String(s) => Ok(Object::String(s.clone())),
+            Primary::Interpolation(parts) => {
+                let mut res = String::new();
+                for p in parts {
+                    match p {
+                        StrPart::Str(s) => res.push_str(s),
+                        StrPart::Expr(e) => {
+                            let v = e.evaluate(env.clone())?;
+                            // `toString()` runs once even if an await after it replays
+                            let s = generator::replayed(|| Ok(Object::String(v.stringify()?)))?;
+                            res.push_str(&s.to_string());
+                        }
+                    }
+                }
+                Ok(Object::String(res))
+            }
             Primary::Boolean(b) => Ok(Object::Boolean(*b)),
+            Primary::List(elems) => {
+                let elems = elems
+                    .as_ref()
+                    .map_or(Ok(vec![]), |x| x.evaluate(env.clone()))?;
+                Ok(list::new_list(elems))
+            }
+            Primary::Map(entries) => {
+                let mut res = vec![];
+                for (k, v) in entries {
+                    res.push((k.evaluate(env.clone())?, v.evaluate(env.clone())?));
+                }
+                map::new_map(res)
+            }
             Primary::Identifier(id) => {
-                let ev = find_id(id, Some(env)).ok_or(RuntimeError {
-                    err: format!("unbound variable {}", id),
-                })?;
+                let ev = find_id(id, Some(env))
+                    .ok_or(RuntimeError::new(format!("unbound variable {}", id)))?;
                 let val = ev.borrow();
                 Ok(val.values.get(id).unwrap().clone())
             }
             Primary::SuperId(_) => todo!(),
-            Primary::This => Ok(Object::Object(Class {
-                name: "this".into(),
-                env: env.clone(),
-            })),
+            Primary::This => match find_id(&"this".into(), Some(env.clone())) {
+                Some(ev) => Ok(ev.borrow().values["this"].clone()),
+                None => Ok(Object::Object(Class {
+                    name: "this".into(),
+                    env: env.clone(),
+                    class: None,
+                })),
+            },
             Primary::Nil => Ok(Object::Nil),
         }
     }
@@ -232,7 +426,13 @@ impl Arguments {
             .as_ref()
             .map_or(Ok(vec![]), |x| x.evaluate(env.clone()))?;
         let this = self.expr.evaluate(env)?;
-        rest.push(this);
+        if !self.spread {
+            rest.push(this);
+        } else if let Object::List(list) = this {
+            rest.extend(list.borrow().iter().cloned());
+        } else {
+            return Err(RuntimeError::new("Can only spread lists.".into()));
+        }
         Ok(rest)
     }
 }
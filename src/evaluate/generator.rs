@@ -0,0 +1,521 @@
+use super::*;
+use statements::{get_do_or_not, Items};
+use std::cell::Cell;
+
+pub type Generator = Rc<RefCell<GeneratorState>>;
+
+/// A function body that can suspend at a `yield` or an `await`.
+pub struct GeneratorState {
+    pub name: String,
+    /// The statements the body is suspended inside of, innermost last.
+    frames: Vec<Frame>,
+    /// A value resumed by `hasNext()` that `next()` has not handed out yet.
+    peeked: Option<Object>,
+    replay: Replay,
+    retry: Option<(Declaration, Rc<RefCell<Env>>)>,
+    returned: Object,
+}
+
+pub enum Step {
+    Yield(Object),
+    Await(Object),
+    Done(Object),
+}
+
+/// Results of the expressions a statement evaluated before an `await`
+/// suspended it, given again when the statement reruns.
+#[derive(Default)]
+struct Replay {
+    done: Vec<Option<(usize, Result<Object, RuntimeError>)>>,
+    started: usize,
+    waiting: Option<(usize, usize, Object)>,
+}
+
+thread_local! {
+    static REPLAY: RefCell<Option<Replay>> = const { RefCell::new(None) };
+    static REPLAYING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn swap_replay(replay: Option<Replay>) -> Option<Replay> {
+    REPLAYING.set(replay.is_some());
+    REPLAY.replace(replay)
+}
+
+enum Entry {
+    NoReplay,
+    Done(Result<Object, RuntimeError>),
+    Run(usize),
+}
+
+fn enter() -> Entry {
+    if !REPLAYING.get() {
+        return Entry::NoReplay;
+    }
+    REPLAY.with_borrow_mut(|r| {
+        let Some(r) = r.as_mut() else {
+            return Entry::NoReplay;
+        };
+        let i = r.started;
+        r.started += 1;
+        match r.done.get(i) {
+            Some(Some((end, res))) => {
+                r.started = *end;
+                Entry::Done(res.clone())
+            }
+            Some(None) => Entry::Run(i),
+            None => {
+                r.done.push(None);
+                Entry::Run(i)
+            }
+        }
+    })
+}
+
+/// Evaluates an expression, unless a replay already has its result.
+#[inline]
+pub fn replayed(
+    eval: impl FnOnce() -> Result<Object, RuntimeError>,
+) -> Result<Object, RuntimeError> {
+    if !REPLAYING.get() {
+        return eval();
+    }
+    let i = match enter() {
+        Entry::NoReplay => return eval(),
+        Entry::Done(res) => return res,
+        Entry::Run(i) => i,
+    };
+    let res = eval();
+    REPLAY.with_borrow_mut(|r| {
+        // an `await` below it suspended, so it did not finish
+        if let Some(r) = r.as_mut().filter(|r| r.waiting.is_none()) {
+            r.done[i] = Some((r.started, res.clone()));
+        }
+    });
+    res
+}
+
+/// Suspends the body until `value` settles; the statement reruns then.
+pub fn await_value(
+    value: impl FnOnce() -> Result<Object, RuntimeError>,
+) -> Result<Object, RuntimeError> {
+    let i = match enter() {
+        Entry::NoReplay => unreachable!("the resolver only allows awaits in async functions"),
+        Entry::Done(res) => return res,
+        Entry::Run(i) => i,
+    };
+    let value = value()?;
+    REPLAY.with_borrow_mut(|r| {
+        if let Some(r) = r.as_mut() {
+            r.waiting = Some((i, r.started, value));
+        }
+    });
+    Err(RuntimeError::new("Suspended.".into()))
+}
+
+/// Runs a call of another function outside of the caller's replay.
+pub fn without_replay<T>(f: impl FnOnce() -> T) -> T {
+    if !REPLAYING.get() {
+        return f();
+    }
+    let replay = swap_replay(None);
+    let res = f();
+    swap_replay(replay);
+    res
+}
+
+/// What is left to run of a statement a `yield` or `await` is inside of.
+enum Frame {
+    Block {
+        decls: Vec<Declaration>,
+        next: usize,
+        env: Rc<RefCell<Env>>,
+    },
+    While {
+        stmt: WhileStmt,
+        env: Rc<RefCell<Env>>,
+    },
+    For {
+        stmt: ForStmt,
+        env: Rc<RefCell<Env>>,
+        started: bool,
+    },
+    ForIn {
+        binding: Binding,
+        items: Items,
+        body: Statement,
+        env: Rc<RefCell<Env>>,
+    },
+    Try {
+        stmt: TryStmt,
+        env: Rc<RefCell<Env>>,
+        stage: TryStage,
+        /// How the body or `catch` ended, to carry on with after `finally`.
+        pending: Option<Result<Completion, RuntimeError>>,
+    },
+}
+
+#[derive(PartialEq)]
+enum TryStage {
+    Body,
+    Catch,
+    Finally,
+}
+
+impl Frame {
+    fn block(block: &Block, env: Rc<RefCell<Env>>) -> Self {
+        Frame::Block {
+            decls: block.0.clone(),
+            next: 0,
+            env,
+        }
+    }
+}
+
+pub fn new_generator(name: &str, body: &Block, env: Rc<RefCell<Env>>) -> Object {
+    Object::Generator(Rc::new(RefCell::new(GeneratorState::new(name, body, env))))
+}
+
+pub fn next(generator: &Generator) -> Result<Option<Object>, RuntimeError> {
+    let mut state = generator
+        .try_borrow_mut()
+        .map_err(|_| RuntimeError::new("Generator is already running.".into()))?;
+    if let Some(v) = state.peeked.take() {
+        return Ok(Some(v));
+    }
+    match state.resume(None)? {
+        Step::Yield(v) => Ok(Some(v)),
+        Step::Done(_) => Ok(None),
+        Step::Await(_) => unreachable!("the resolver only allows awaits in async functions"),
+    }
+}
+
+fn has_next(generator: &Generator) -> Result<bool, RuntimeError> {
+    let v = next(generator)?;
+    let more = v.is_some();
+    generator.borrow_mut().peeked = v;
+    Ok(more)
+}
+
+pub fn method(generator: &Generator, name: &str) -> Result<Object, RuntimeError> {
+    let generator = generator.clone();
+    let fun: NativeFn = match name {
+        // like an iterator, `next()` gives nil once there are no more values
+        "next" => Rc::new(move |passed, _, _, _| {
+            let [] = crate::native_function::args(passed)?;
+            Ok(next(&generator)?.unwrap_or(Object::Nil))
+        }),
+        "hasNext" => Rc::new(move |passed, _, _, _| {
+            let [] = crate::native_function::args(passed)?;
+            Ok(Object::Boolean(has_next(&generator)?))
+        }),
+        _ => return Err(RuntimeError::new(format!("Undefined property '{name}'."))),
+    };
+
+    Ok(Object::Function(ExFn {
+        name: name.into(),
+        body: Block(vec![]),
+        params: vec![],
+        env: Env::new_box_it(None),
+        fun,
+        class: None,
+    }))
+}
+
+impl GeneratorState {
+    pub fn new(name: &str, body: &Block, env: Rc<RefCell<Env>>) -> Self {
+        let frames = vec![Frame::block(body, env)];
+        Self {
+            name: name.into(),
+            frames,
+            peeked: None,
+            replay: Replay::default(),
+            retry: None,
+            returned: Object::Nil,
+        }
+    }
+
+    /// `awaited` is the outcome of the `await` it last stopped at.
+    pub fn resume(
+        &mut self,
+        awaited: Option<Result<Object, RuntimeError>>,
+    ) -> Result<Step, RuntimeError> {
+        if let (Some((i, end, _)), Some(res)) = (self.replay.waiting.take(), awaited) {
+            self.replay.done[i] = Some((end, res));
+        }
+        let outer = swap_replay(Some(std::mem::take(&mut self.replay)));
+        let res = self.run();
+        self.replay = swap_replay(outer).unwrap_or_default();
+        if res.is_err() {
+            // an error ends the body, like it ends a function call
+            self.frames.clear();
+        }
+        res
+    }
+
+    fn run(&mut self) -> Result<Step, RuntimeError> {
+        while !self.frames.is_empty() {
+            let res = self.advance();
+            // the replay only lasts until the statement gets past its awaits
+            let waiting = REPLAY.with_borrow_mut(|r| {
+                let r = r.as_mut()?;
+                r.started = 0;
+                if r.waiting.is_none() {
+                    r.done.clear();
+                }
+                r.waiting.as_ref().map(|(_, _, v)| v.clone())
+            });
+            match (res, waiting) {
+                (_, Some(v)) => return Ok(Step::Await(v)),
+                (Ok(Some(step)), None) => return Ok(step),
+                (Ok(None), None) => {}
+                (Err(e), None) => self.throw(e)?,
+            }
+        }
+        Ok(Step::Done(std::mem::replace(
+            &mut self.returned,
+            Object::Nil,
+        )))
+    }
+
+    fn advance(&mut self) -> Result<Option<Step>, RuntimeError> {
+        if let Some((decl, env)) = self.retry.take() {
+            return self.run_decl(decl, env);
+        }
+        let Some(frame) = self.frames.last_mut() else {
+            return Ok(None);
+        };
+        let (stmt, env) = match frame {
+            Frame::Block { decls, next, env } => {
+                let Some(decl) = decls.get(*next).cloned() else {
+                    self.frames.pop();
+                    return Ok(None);
+                };
+                *next += 1;
+                let env = env.clone();
+                return self.run_decl(decl, env);
+            }
+            Frame::While { stmt, env } => {
+                if !get_do_or_not(stmt.pred.evaluate(env.clone())?) {
+                    self.frames.pop();
+                    return Ok(None);
+                }
+                (*stmt.stmt.clone(), env.clone())
+            }
+            Frame::For { stmt, env, started } => {
+                if let Some(post) = stmt.thrd_expr.as_ref().filter(|_| *started) {
+                    post.evaluate(env.clone())?;
+                }
+                if let Some(pred) = &stmt.scnd_expr {
+                    if !get_do_or_not(pred.evaluate(env.clone())?) {
+                        self.frames.pop();
+                        return Ok(None);
+                    }
+                }
+                *started = true;
+                (*stmt.body.clone(), env.clone())
+            }
+            Frame::ForIn {
+                binding,
+                items,
+                body,
+                env,
+            } => {
+                let Some(item) = items.next() else {
+                    self.frames.pop();
+                    return Ok(None);
+                };
+                let scope = Env::new_box_it(Some(env.clone()));
+                binding.bind(item?, &scope)?;
+                (body.clone(), scope)
+            }
+            // the block above it ended normally
+            Frame::Try {
+                stmt,
+                env,
+                stage,
+                pending,
+            } => {
+                if *stage != TryStage::Finally {
+                    if let Some(finally) = &stmt.finally {
+                        let block = Frame::block(finally, Env::new_box_it(Some(env.clone())));
+                        *stage = TryStage::Finally;
+                        *pending = Some(Ok(Completion::Normal));
+                        self.frames.push(block);
+                        return Ok(None);
+                    }
+                }
+                let pending = pending.take();
+                self.frames.pop();
+                match pending {
+                    Some(Err(e)) => return Err(e),
+                    Some(Ok(c)) => self.complete(c),
+                    None => {}
+                }
+                return Ok(None);
+            }
+        };
+        self.run_decl(Declaration::Statement(stmt), env)
+    }
+
+    fn run_decl(
+        &mut self,
+        decl: Declaration,
+        env: Rc<RefCell<Env>>,
+    ) -> Result<Option<Step>, RuntimeError> {
+        let res = match &decl {
+            Declaration::Statement(stmt) => self.start(stmt, env.clone()),
+            decl => decl.execute(env.clone()).map(|c| {
+                self.complete(c);
+                None
+            }),
+        };
+        let suspended = REPLAY.with_borrow(|r| r.as_ref().is_some_and(|r| r.waiting.is_some()));
+        if suspended {
+            self.retry = Some((decl, env));
+        }
+        res
+    }
+
+    /// Gives `e` back when no enclosing `try` handles it.
+    fn throw(&mut self, e: RuntimeError) -> Result<(), RuntimeError> {
+        while let Some(frame) = self.frames.pop() {
+            let Frame::Try {
+                stmt, env, stage, ..
+            } = frame
+            else {
+                continue;
+            };
+            let scope = Env::new_box_it(Some(env.clone()));
+            let (stage, pending, block) = match (stage, &stmt.catch, &stmt.finally) {
+                (TryStage::Body, Some((name, block)), _) => {
+                    scope.borrow_mut().values.insert(name.clone(), e.value());
+                    (TryStage::Catch, None, block.clone())
+                }
+                (TryStage::Body | TryStage::Catch, _, Some(block)) => {
+                    (TryStage::Finally, Some(Err(e)), block.clone())
+                }
+                // an error in the finally block replaces the one before
+                _ => continue,
+            };
+            self.frames.push(Frame::Try {
+                stmt,
+                env,
+                stage,
+                pending,
+            });
+            self.frames.push(Frame::block(&block, scope));
+            return Ok(());
+        }
+        Err(e)
+    }
+
+    fn start(
+        &mut self,
+        stmt: &Statement,
+        env: Rc<RefCell<Env>>,
+    ) -> Result<Option<Step>, RuntimeError> {
+        match stmt {
+            Statement::YieldStmt(yield_stmt) => match &yield_stmt.expr {
+                Some(e) => Ok(Some(Step::Yield(e.evaluate(env)?))),
+                None => Ok(Some(Step::Yield(Object::Nil))),
+            },
+            Statement::Block(block) => {
+                self.frames
+                    .push(Frame::block(block, Env::new_box_it(Some(env))));
+                Ok(None)
+            }
+            Statement::TryStmt(try_stmt) => {
+                let scope = Env::new_box_it(Some(env.clone()));
+                self.frames.push(Frame::Try {
+                    stmt: try_stmt.clone(),
+                    env,
+                    stage: TryStage::Body,
+                    pending: None,
+                });
+                self.frames.push(Frame::block(&try_stmt.body, scope));
+                Ok(None)
+            }
+            Statement::IfStmt(if_stmt) => {
+                if get_do_or_not(if_stmt.pred.evaluate(env.clone())?) {
+                    self.start(&if_stmt.if_stmt, env)
+                } else if let Some(el) = &if_stmt.else_stmt {
+                    self.start(el, env)
+                } else {
+                    Ok(None)
+                }
+            }
+            Statement::MatchStmt(match_stmt) => match match_stmt.select(env)? {
+                Some((body, env)) => self.start(body, env),
+                None => Ok(None),
+            },
+            Statement::WhileStmt(while_stmt) => {
+                let stmt = while_stmt.clone();
+                self.frames.push(Frame::While { stmt, env });
+                Ok(None)
+            }
+            Statement::ForStmt(for_stmt) => {
+                let env = Env::new_box_it(Some(env));
+                for_stmt.first_dec.execute(env.clone())?;
+                let stmt = for_stmt.clone();
+                let started = false;
+                self.frames.push(Frame::For { stmt, env, started });
+                Ok(None)
+            }
+            Statement::ForInStmt(for_in_stmt) => {
+                self.frames.push(Frame::ForIn {
+                    binding: for_in_stmt.binding.clone(),
+                    items: for_in_stmt.items(env.clone())?,
+                    body: *for_in_stmt.body.clone(),
+                    env,
+                });
+                Ok(None)
+            }
+            stmt => {
+                let c = stmt.execute(env)?;
+                self.complete(c);
+                Ok(None)
+            }
+        }
+    }
+
+    fn complete(&mut self, c: Completion) {
+        if let Completion::Normal = c {
+            return;
+        }
+        while let Some(frame) = self.frames.last_mut() {
+            match (frame, &c) {
+                (
+                    Frame::Try {
+                        stmt:
+                            TryStmt {
+                                finally: Some(finally),
+                                ..
+                            },
+                        env,
+                        stage,
+                        pending,
+                    },
+                    _,
+                ) if *stage != TryStage::Finally => {
+                    let block = Frame::block(finally, Env::new_box_it(Some(env.clone())));
+                    *stage = TryStage::Finally;
+                    *pending = Some(Ok(c));
+                    self.frames.push(block);
+                    return;
+                }
+                (Frame::Block { .. } | Frame::Try { .. }, _) => {}
+                (_, Completion::Continue) => return,
+                (_, Completion::Break) => {
+                    self.frames.pop();
+                    return;
+                }
+                _ => {}
+            }
+            self.frames.pop();
+        }
+        // what a generator returns is not one of its values
+        if let Completion::Return(v) = c {
+            self.returned = v;
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use super::*;
+use crate::native_function::args;
+
+pub type List = Rc<RefCell<Vec<Object>>>;
+
+pub fn new_list(elems: Vec<Object>) -> Object {
+    Object::List(Rc::new(RefCell::new(elems)))
+}
+
+/// Turns `i` into a position in a list of length `len`. `insert` may also
+/// append, so it passes `len + 1` to allow one past the end.
+pub fn index(i: &Object, len: usize) -> Result<usize, RuntimeError> {
+    let n = i
+        .get_number()
+        .ok_or(RuntimeError::new("Index must be a number.".into()))?;
+    if n.fract() != 0.0 {
+        return Err(RuntimeError::new("Index must be an integer.".into()));
+    }
+    if n < 0.0 || n >= len as f64 {
+        return Err(RuntimeError::new(format!(
+            "Index {n} out of range for length {len}."
+        )));
+    }
+    Ok(n as usize)
+}
+
+pub fn get(list: &List, i: &Object) -> Result<Object, RuntimeError> {
+    let list = list.borrow();
+    let i = index(i, list.len())?;
+    Ok(list[i].clone())
+}
+
+pub fn set(list: &List, i: &Object, value: Object) -> Result<Object, RuntimeError> {
+    let mut list = list.borrow_mut();
+    let i = index(i, list.len())?;
+    list[i] = value.clone();
+    Ok(value)
+}
+
+/// Looks up a native method on `list`, bound to that list.
+pub fn method(list: &List, name: &str) -> Result<Object, RuntimeError> {
+    let list = list.clone();
+    let fun: NativeFn = match name {
+        "len" => Rc::new(move |passed, _, _, _| {
+            let [] = args(passed)?;
+            Ok(Object::Number(list.borrow().len() as f64))
+        }),
+        "push" => Rc::new(move |passed, _, _, _| {
+            let [value] = args(passed)?;
+            list.borrow_mut().push(value);
+            Ok(Object::Nil)
+        }),
+        "pop" => Rc::new(move |passed, _, _, _| {
+            let [] = args(passed)?;
+            list.borrow_mut()
+                .pop()
+                .ok_or(RuntimeError::new("Can't pop from an empty list.".into()))
+        }),
+        "insert" => Rc::new(move |passed, _, _, _| {
+            let [i, value] = args(passed)?;
+            let mut list = list.borrow_mut();
+            let i = index(&i, list.len() + 1)?;
+            list.insert(i, value);
+            Ok(Object::Nil)
+        }),
+        "remove" => Rc::new(move |passed, _, _, _| {
+            let [i] = args(passed)?;
+            let mut list = list.borrow_mut();
+            let i = index(&i, list.len())?;
+            Ok(list.remove(i))
+        }),
+        "slice" => Rc::new(move |passed, _, _, _| {
+            let list = list.borrow();
+            let (start, end) = match passed.len() {
+                1 => {
+                    let [start] = args(passed)?;
+                    (index(&start, list.len() + 1)?, list.len())
+                }
+                _ => {
+                    let [start, end] = args(passed)?;
+                    let start = index(&start, list.len() + 1)?;
+                    let end = index(&end, list.len() + 1)?;
+                    (start, end.max(start))
+                }
+            };
+            Ok(new_list(list[start..end].to_vec()))
+        }),
+        _ => return Err(RuntimeError::new(format!("Undefined property '{name}'."))),
+    };
+
+    Ok(Object::Function(ExFn {
+        name: name.into(),
+        body: Block(vec![]),
+        params: vec![],
+        env: Env::new_box_it(None),
+        fun,
+        class: None,
+    }))
+}
+
+/// Splits `value` into exactly `n` values, for destructuring.
+pub fn unpack(value: Object, n: usize) -> Result<Vec<Object>, RuntimeError> {
+    let Object::List(list) = value else {
+        return Err(RuntimeError::new("Can only destructure lists.".into()));
+    };
+    let values = list.borrow().clone();
+    if values.len() != n {
+        return Err(RuntimeError::new(format!(
+            "Expect {n} values to destructure but got {}.",
+            values.len()
+        )));
+    }
+    Ok(values)
+}
@@ -0,0 +1,143 @@
+use std::hash::{Hash, Hasher};
+
+use indexmap::IndexMap;
+
+use super::*;
+use crate::native_function::args;
+
+pub type Map = Rc<RefCell<IndexMap<Key, Object>>>;
+
+#[derive(Clone)]
+pub enum Key {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Nil,
+}
+
+impl Key {
+    // 0 and -0 are equal as numbers, so they must hash the same too
+    fn bits(n: f64) -> u64 {
+        if n == 0.0 {
+            0
+        } else {
+            n.to_bits()
+        }
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Key::Number(a), Key::Number(b)) => Key::bits(*a) == Key::bits(*b),
+            (Key::String(a), Key::String(b)) => a == b,
+            (Key::Boolean(a), Key::Boolean(b)) => a == b,
+            (Key::Nil, Key::Nil) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Key::Number(n) => Key::bits(*n).hash(state),
+            Key::String(s) => s.hash(state),
+            Key::Boolean(b) => b.hash(state),
+            Key::Nil => {}
+        }
+    }
+}
+
+impl TryFrom<&Object> for Key {
+    type Error = RuntimeError;
+
+    fn try_from(value: &Object) -> Result<Self, Self::Error> {
+        match value {
+            Object::Number(n) => Ok(Key::Number(*n)),
+            Object::String(s) => Ok(Key::String(s.clone())),
+            Object::Boolean(b) => Ok(Key::Boolean(*b)),
+            Object::Nil => Ok(Key::Nil),
+            _ => Err(RuntimeError::new(
+                "Map keys must be numbers, strings, booleans or nil.".into(),
+            )),
+        }
+    }
+}
+
+impl From<&Key> for Object {
+    fn from(value: &Key) -> Self {
+        match value {
+            Key::Number(n) => Object::Number(*n),
+            Key::String(s) => Object::String(s.clone()),
+            Key::Boolean(b) => Object::Boolean(*b),
+            Key::Nil => Object::Nil,
+        }
+    }
+}
+
+pub fn new_map(entries: Vec<(Object, Object)>) -> Result<Object, RuntimeError> {
+    let mut map = IndexMap::new();
+    for (k, v) in entries {
+        map.insert(Key::try_from(&k)?, v);
+    }
+    Ok(Object::Map(Rc::new(RefCell::new(map))))
+}
+
+pub fn get(map: &Map, key: &Object) -> Result<Object, RuntimeError> {
+    let k = Key::try_from(key)?;
+    map.borrow()
+        .get(&k)
+        .cloned()
+        .ok_or(RuntimeError::new(format!("Undefined key '{key}'.")))
+}
+
+pub fn set(map: &Map, key: &Object, value: Object) -> Result<Object, RuntimeError> {
+    let k = Key::try_from(key)?;
+    map.borrow_mut().insert(k, value.clone());
+    Ok(value)
+}
+
+pub fn method(map: &Map, name: &str) -> Result<Object, RuntimeError> {
+    let map = map.clone();
+    let fun: NativeFn = match name {
+        "len" => Rc::new(move |passed, _, _, _| {
+            let [] = args(passed)?;
+            Ok(Object::Number(map.borrow().len() as f64))
+        }),
+        "keys" => Rc::new(move |passed, _, _, _| {
+            let [] = args(passed)?;
+            Ok(list::new_list(
+                map.borrow().keys().map(Object::from).collect(),
+            ))
+        }),
+        "values" => Rc::new(move |passed, _, _, _| {
+            let [] = args(passed)?;
+            Ok(list::new_list(map.borrow().values().cloned().collect()))
+        }),
+        "has" => Rc::new(move |passed, _, _, _| {
+            let [key] = args(passed)?;
+            let k = Key::try_from(&key)?;
+            Ok(Object::Boolean(map.borrow().contains_key(&k)))
+        }),
+        // removing a key that isn't there is not an error, it just gives nil
+        "remove" => Rc::new(move |passed, _, _, _| {
+            let [key] = args(passed)?;
+            let k = Key::try_from(&key)?;
+            Ok(map.borrow_mut().shift_remove(&k).unwrap_or(Object::Nil))
+        }),
+        _ => return Err(RuntimeError::new(format!("Undefined property '{name}'."))),
+    };
+
+    Ok(Object::Function(ExFn {
+        name: name.into(),
+        body: Block(vec![]),
+        params: vec![],
+        env: Env::new_box_it(None),
+        fun,
+        class: None,
+    }))
+}
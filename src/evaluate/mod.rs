@@ -1,18 +1,48 @@
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{cell::RefCell, rc::Rc};
 
 use crate::ast::{declarations::*, expressions::*, statements::*};
+pub use enums::{Enum, Variant};
 use environment::Env;
+pub use generator::Generator;
+pub use list::List;
+pub use map::Map;
+pub use module::Module;
 pub use object::*;
+pub use promise::Promise;
+pub use records::Record;
 
 pub mod declarations;
+pub mod enums;
 pub mod environment;
+pub mod event_loop;
 pub mod expressions;
+pub mod generator;
+pub mod list;
+pub mod map;
+pub mod module;
 pub mod object;
 pub mod operations;
+pub mod promise;
+pub mod records;
 pub mod statements;
 
 pub trait Eval {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError>;
+    fn eval(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError>;
+
+    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+        generator::replayed(|| self.eval(env))
+    }
+}
+
+pub trait Exec {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError>;
+}
+
+pub enum Completion {
+    Normal,
+    Return(Object),
+    Break,
+    Continue,
 }
 
 pub trait EvalBinOp {
@@ -23,7 +53,73 @@ pub trait EvalUnOp {
     fn evaluate(&self, exp: Object) -> Result<Object, RuntimeError>;
 }
 
-#[derive(Clone, Debug)]
+/// `thrown` keeps a value thrown with `throw` as it is.
+#[derive(Clone)]
 pub struct RuntimeError {
     pub err: String,
+    pub line: Option<usize>,
+    pub thrown: Option<Box<Object>>,
+}
+
+impl RuntimeError {
+    pub fn new(err: String) -> Self {
+        Self {
+            err,
+            line: None,
+            thrown: None,
+        }
+    }
+
+    pub fn throw(v: Object) -> Self {
+        Self {
+            err: v.to_string(),
+            line: None,
+            thrown: Some(Box::new(v)),
+        }
+    }
+
+    /// Keeps the line an inner expression already recorded.
+    pub fn at(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    pub fn value(&self) -> Object {
+        if let Some(v) = &self.thrown {
+            return v.as_ref().clone();
+        }
+        let env = Env::new_box_it(None);
+        let line = self.line.map_or(Object::Nil, |l| Object::Number(l as f64));
+        env.borrow_mut()
+            .values
+            .insert("message".into(), Object::String(self.err.clone()));
+        env.borrow_mut().values.insert("line".into(), line);
+        Object::Object(Class {
+            name: "Error".into(),
+            env,
+            class: Some(ERROR_CLASS.with(Rc::clone)),
+        })
+    }
+}
+
+thread_local! {
+    static ERROR_CLASS: Rc<ClassDef> = Rc::new(ClassDef::new(None));
+}
+
+pub fn error_class() -> Object {
+    let def = ERROR_CLASS.with(Rc::clone);
+    Object::Function(ExFn {
+        name: "Error".into(),
+        params: vec![],
+        body: Block(vec![]),
+        env: Env::new_box_it(None),
+        class: Some(def.clone()),
+        fun: Rc::new(move |_, _, _, _| {
+            Ok(Object::Object(Class {
+                name: "Error".into(),
+                env: Env::new_box_it(None),
+                class: Some(def.clone()),
+            }))
+        }),
+    })
 }
@@ -0,0 +1,151 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::*;
+use crate::{
+    native_function,
+    parse::{self, Parser},
+    resolve::{Resolve, Resolver},
+    scan::Scanner,
+};
+
+#[derive(Clone)]
+pub struct Module {
+    pub name: String,
+    pub env: Rc<RefCell<Env>>,
+    /// The only names reachable through the module.
+    pub members: Rc<Vec<String>>,
+}
+
+impl Module {
+    pub fn get(&self, name: &str) -> Result<Object, RuntimeError> {
+        let value = match self.members.iter().any(|m| m == name) {
+            true => self.env.borrow().values.get(name).cloned(),
+            false => None,
+        };
+        value.ok_or(RuntimeError::new(format!(
+            "Module '{}' has no member '{name}'.",
+            self.name
+        )))
+    }
+}
+
+#[derive(Default)]
+struct Loader {
+    // modules that have finished running
+    cache: HashMap<PathBuf, Module>,
+    // files that are running right now, the innermost one last
+    loading: Vec<PathBuf>,
+}
+
+thread_local! {
+    static LOADER: RefCell<Loader> = RefCell::new(Loader::default());
+}
+
+pub fn run_as<T>(path: PathBuf, f: impl FnOnce() -> T) -> T {
+    LOADER.with_borrow_mut(|l| l.loading.push(path));
+    let res = f();
+    LOADER.with_borrow_mut(|l| l.loading.pop());
+    res
+}
+
+pub fn import(path: &str) -> Result<Module, RuntimeError> {
+    let base = LOADER.with_borrow(|l| {
+        l.loading
+            .last()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    });
+    let full = fs::canonicalize(base.join(path))
+        .map_err(|_| RuntimeError::new(format!("Can't find module '{path}'.")))?;
+
+    if let Some(module) = LOADER.with_borrow(|l| l.cache.get(&full).cloned()) {
+        return Ok(Module {
+            name: path.into(),
+            ..module
+        });
+    }
+    if let Some(cycle) = LOADER.with_borrow(|l| cycle(&l.loading, &full)) {
+        return Err(RuntimeError::new(format!("Import cycle: {cycle}.")));
+    }
+
+    let src = fs::read_to_string(&full)
+        .map_err(|_| RuntimeError::new(format!("Can't read module '{path}'.")))?;
+    let mut scanner = Scanner::new(src);
+    scanner.scan();
+    if let Some(e) = scanner.errors.first() {
+        return Err(RuntimeError::new(format!(
+            "[line {}] Error: {} (in module '{path}')",
+            e.line, e.tok
+        )));
+    }
+    parse::settle_comments(&mut scanner);
+    let mut parser = Parser::new(&scanner.tokens);
+    parser.parse().map_err(|e| {
+        RuntimeError::new(format!(
+            "[line {}] Error at '{}': {} (in module '{path}')",
+            e.tok.line, e.tok.lexeme, e.err
+        ))
+    })?;
+    let program = parser.program.unwrap();
+    let mut resolver = Resolver::new();
+    program.resolve(&mut resolver).map_err(|e| {
+        RuntimeError::new(format!(
+            "[line {}] Error at '{}': {} (in module '{path}')",
+            e.tok.line, e.tok.lexeme, e.err
+        ))
+    })?;
+    for w in &resolver.warnings {
+        eprintln!(
+            "[line {}] Warning at '{}': {} (in module '{path}')",
+            w.tok.line, w.tok.lexeme, w.err
+        );
+    }
+
+    let env = Env::new_box_it(None);
+    native_function::define_globals(&env);
+    run_as(full.clone(), || {
+        program
+            .declarations
+            .iter()
+            .try_for_each(|d| d.execute(env.clone()).map(|_| ()))
+    })?;
+    let module = Module {
+        name: path.into(),
+        env,
+        members: Rc::new(declared(&program)),
+    };
+    LOADER.with_borrow_mut(|l| l.cache.insert(full, module.clone()));
+    Ok(module)
+}
+
+/// Leaves out the natives and whatever `program` imports.
+fn declared(program: &crate::ast::Program) -> Vec<String> {
+    let mut names = vec![];
+    for d in &program.declarations {
+        match d {
+            Declaration::ClassDecl(c) => names.push(c.name.clone()),
+            Declaration::EnumDecl(e) => names.push(e.name.clone()),
+            Declaration::FunDecl(f) => names.push(f.0.name.clone()),
+            Declaration::RecordDecl(r) => names.push(r.name.clone()),
+            Declaration::VarDecl(VarDecl::Name(name, _)) => names.push(name.clone()),
+            Declaration::VarDecl(VarDecl::Tuple(vars, _)) => names.extend(vars.iter().cloned()),
+            Declaration::ImportDecl(_) | Declaration::Statement(_) => {}
+        }
+    }
+    names
+}
+
+fn cycle(loading: &[PathBuf], path: &PathBuf) -> Option<String> {
+    let start = loading.iter().position(|p| p == path)?;
+    let names = loading[start..]
+        .iter()
+        .chain([path])
+        .map(|p| p.file_name().unwrap_or_default().to_string_lossy())
+        .collect::<Vec<_>>();
+    Some(names.join(" -> "))
+}
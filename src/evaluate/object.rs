@@ -1,7 +1,8 @@
-use std::{cell::RefCell, fmt::Display, rc::Rc};
+use std::{cell::RefCell, convert::Infallible, fmt::Display, rc::Rc};
 
 use super::*;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone)]
 pub enum Object {
     Number(f64),
@@ -9,7 +10,14 @@ pub enum Object {
     Boolean(bool),
     Object(Class),
     Function(ExFn),
-    Return(Box<Object>),
+    List(List),
+    Map(Map),
+    Module(Module),
+    Enum(Enum),
+    Variant(Variant),
+    Record(Record),
+    Generator(Generator),
+    Promise(Promise),
     Nil,
 }
 
@@ -19,30 +27,248 @@ pub struct ExFn {
     pub body: Block,
     pub params: Vec<String>,
     pub env: Rc<RefCell<Env>>,
-    pub fun: Arc<
-        dyn Fn(Vec<Object>, &Vec<String>, &Block, Rc<RefCell<Env>>) -> Result<Object, RuntimeError>,
-    >,
+    pub fun: NativeFn,
+    pub class: Option<Rc<ClassDef>>,
 }
 
+pub type NativeFn =
+    Rc<dyn Fn(Vec<Object>, &Vec<String>, &Block, Rc<RefCell<Env>>) -> Result<Object, RuntimeError>>;
+
 #[derive(Clone)]
 pub struct Class {
     pub name: String,
     pub env: Rc<RefCell<Env>>,
+    pub class: Option<Rc<ClassDef>>,
+}
+
+/// Each table's `next` is the superclass's, never an enclosing scope.
+pub struct ClassDef {
+    /// Getters and setters are kept under `get name` and `set name`.
+    pub methods: Rc<RefCell<Env>>,
+    pub statics: Rc<RefCell<Env>>,
+    pub fields: Rc<RefCell<Env>>,
+    pub superclass: Option<Rc<ClassDef>>,
 }
 
-impl<'a> Display for Object {
+impl ExFn {
+    pub fn call(&self, args: Vec<Object>) -> Result<Object, RuntimeError> {
+        generator::without_replay(|| {
+            self.fun.as_ref()(args, &self.params, &self.body, self.env.clone())
+        })
+    }
+
+    /// Takes the instance as the first argument and binds it to `this`.
+    pub fn into_method(self) -> ExFn {
+        let fun = self.fun.clone();
+        ExFn {
+            fun: Rc::new(move |passed, params, body, env| {
+                let Some((this, passed)) = passed.split_first() else {
+                    return Err(crate::native_function::arity_error("at least 1", 0));
+                };
+                let env = Env::new_box_it(Some(env));
+                env.borrow_mut().values.insert("this".into(), this.clone());
+                fun(passed.to_vec(), params, body, env)
+            }),
+            ..self
+        }
+    }
+}
+
+impl ClassDef {
+    pub fn new(superclass: Option<Rc<ClassDef>>) -> Self {
+        Self {
+            methods: Env::new_box_it(superclass.as_ref().map(|s| s.methods.clone())),
+            statics: Env::new_box_it(superclass.as_ref().map(|s| s.statics.clone())),
+            fields: Env::new_box_it(superclass.as_ref().map(|s| s.fields.clone())),
+            superclass,
+        }
+    }
+
+    pub fn is_a(self: &Rc<Self>, class: &Rc<ClassDef>) -> bool {
+        let mut def = Some(self);
+        while let Some(d) = def {
+            if Rc::ptr_eq(d, class) {
+                return true;
+            }
+            def = d.superclass.as_ref();
+        }
+        false
+    }
+
+    fn method(&self, name: &str) -> Option<ExFn> {
+        let env = environment::find_id(&name.into(), Some(self.methods.clone()))?;
+        let method = env.borrow().values.get(name)?.get_function().cloned();
+        method
+    }
+
+    pub fn get(&self, name: &str) -> Option<Object> {
+        [&self.fields, &self.statics].into_iter().find_map(|table| {
+            let env = environment::find_id(&name.into(), Some(table.clone()))?;
+            let value = env.borrow().values.get(name).cloned();
+            value
+        })
+    }
+
+    /// `otherwise` for a class without any statics or fields.
+    pub fn missing(&self, name: &str, otherwise: &str) -> RuntimeError {
+        let declares = |table: &Rc<RefCell<Env>>| {
+            let mut env = Some(table.clone());
+            while let Some(e) = env {
+                if !e.borrow().values.is_empty() {
+                    return true;
+                }
+                env = e.borrow().next.clone();
+            }
+            false
+        };
+        if declares(&self.fields) || declares(&self.statics) {
+            RuntimeError::new(format!("Undefined property '{name}'."))
+        } else {
+            RuntimeError::new(otherwise.into())
+        }
+    }
+
+    pub fn set(&self, name: &str, value: Object) -> Option<Object> {
+        let env = environment::find_id(&name.into(), Some(self.fields.clone()))?;
+        env.borrow_mut().values.insert(name.into(), value.clone());
+        Some(value)
+    }
+}
+
+impl Class {
+    pub fn accessor(&self, prefix: &str, name: &str) -> Option<ExFn> {
+        self.bound(&format!("{prefix}{name}"))
+    }
+
+    /// Functions in enclosing scopes are never methods.
+    pub fn method(&self, name: &str) -> Option<ExFn> {
+        if let Some(own) = self.env.borrow().values.get(name) {
+            return own.get_function().cloned();
+        }
+        self.bound(name)
+            .or_else(|| self.class.as_ref()?.get(name)?.get_function().cloned())
+    }
+
+    pub fn bound(&self, name: &str) -> Option<ExFn> {
+        let method = self.class.as_ref()?.method(name)?;
+        let this = Object::Object(self.clone());
+        Some(ExFn {
+            fun: Rc::new({
+                let method = method.clone();
+                move |mut passed, _, _, _| {
+                    passed.insert(0, this.clone());
+                    method.call(passed)
+                }
+            }),
+            ..method
+        })
+    }
+}
+
+impl Display for Object {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Ok(v) = self.format_with(&|x| Ok::<_, Infallible>(x.to_string()));
+        write!(f, "{v}")
+    }
+}
+
+thread_local! {
+    /// Instances whose `toString()` is running, printed plainly if they recur.
+    static STRINGIFYING: RefCell<Vec<Rc<RefCell<Env>>>> = const { RefCell::new(vec![]) };
+
+    static FORMATTING: RefCell<Vec<*const ()>> = const { RefCell::new(vec![]) };
+
+    static COMPARING: RefCell<Vec<(*const (), *const ())>> = const { RefCell::new(vec![]) };
+}
+
+fn format_nested<T, E>(
+    value: &Rc<RefCell<T>>,
+    cycle: &str,
+    format: impl FnOnce(&T) -> Result<String, E>,
+) -> Result<String, E> {
+    let ptr = Rc::as_ptr(value) as *const ();
+    if FORMATTING.with_borrow(|s| s.contains(&ptr)) {
+        return Ok(cycle.into());
+    }
+    FORMATTING.with_borrow_mut(|s| s.push(ptr));
+    let res = format(&value.borrow());
+    FORMATTING.with_borrow_mut(|s| s.pop());
+    res
+}
+
+/// A pair already being compared further up counts as equal.
+fn eq_nested<T>(a: &Rc<RefCell<T>>, b: &Rc<RefCell<T>>, eq: impl FnOnce(&T, &T) -> bool) -> bool {
+    if Rc::ptr_eq(a, b) {
+        return true;
+    }
+    let pair = (Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const ());
+    if COMPARING.with_borrow(|s| s.contains(&pair)) {
+        return true;
+    }
+    COMPARING.with_borrow_mut(|s| s.push(pair));
+    let res = eq(&a.borrow(), &b.borrow());
+    COMPARING.with_borrow_mut(|s| s.pop());
+    res
+}
+
+impl Object {
+    fn format_with<E>(&self, inner: &impl Fn(&Object) -> Result<String, E>) -> Result<String, E> {
         use self::Object::*;
+        let join = |values: &[Self]| -> Result<std::string::String, E> {
+            Ok(values
+                .iter()
+                .map(inner)
+                .collect::<Result<Vec<_>, E>>()?
+                .join(", "))
+        };
         let v = match self {
             Number(n) => n.to_string(),
             String(s) => s.clone(),
             Boolean(v) => v.to_string(),
-            Object(v) => format!("<obj {}>", v.name),
+            Object(v) => format!("{} instance", v.name),
             Function(v) => format!("<fn {}>", v.name),
-            Return(object) => object.to_string(),
+            List(v) => format_nested(v, "[...]", |v| Ok(format!("[{}]", join(v)?)))?,
+            Map(v) => format_nested(v, "{...}", |v| {
+                let mut entries = vec![];
+                for (k, v) in v.iter() {
+                    entries.push(format!("{}: {}", Self::from(k), inner(v)?));
+                }
+                Ok(format!("{{{}}}", entries.join(", ")))
+            })?,
+            Module(v) => format!("<module {}>", v.name),
+            Enum(v) => format!("<enum {}>", v.name),
+            Variant(v) if v.values.is_empty() => format!("{}.{}", v.of.name, v.name()),
+            Variant(v) => format!("{}.{}({})", v.of.name, v.name(), join(&v.values)?),
+            Record(v) => {
+                let mut fields = vec![];
+                for (f, x) in v.fields.iter().zip(&v.values) {
+                    fields.push(format!("{f}: {}", inner(x)?));
+                }
+                format!("{}({})", v.name, fields.join(", "))
+            }
+            Generator(v) => format!("<generator {}>", v.borrow().name),
+            Promise(_) => "<promise>".into(),
             Nil => "nil".into(),
         };
-        write!(f, "{v}")
+        Ok(v)
+    }
+
+    pub fn stringify(&self) -> Result<String, RuntimeError> {
+        let Some(cls) = self.get_object() else {
+            return self.format_with(&|x| x.stringify());
+        };
+        let running = STRINGIFYING.with_borrow(|s| s.iter().any(|e| Rc::ptr_eq(e, &cls.env)));
+        let Some(method) = cls.method("toString").filter(|_| !running) else {
+            return Ok(self.to_string());
+        };
+
+        STRINGIFYING.with_borrow_mut(|s| s.push(cls.env.clone()));
+        let res = method.call(vec![]);
+        STRINGIFYING.with_borrow_mut(|s| s.pop());
+        match res? {
+            Object::String(s) => Ok(s),
+            _ => Err(RuntimeError::new("toString() must return a string.".into())),
+        }
     }
 }
 
@@ -71,15 +297,51 @@ impl PartialEq for Object {
                 }
             }
             Object::Object(o) => match other {
-                Object::Object(d) => o.env.borrow().values == d.env.borrow().values,
+                Object::Object(d) => eq_nested(&o.env, &d.env, |o, d| o.values == d.values),
+                _ => false,
+            },
+            Object::List(l) => match other {
+                Object::List(m) => eq_nested(l, m, |l, m| l == m),
+                _ => false,
+            },
+            Object::Map(m) => match other {
+                Object::Map(n) => eq_nested(m, n, |m, n| m == n),
+                _ => false,
+            },
+            Object::Module(m) => match other {
+                Object::Module(n) => Rc::ptr_eq(&m.env, &n.env),
+                _ => false,
+            },
+            Object::Enum(e) => match other {
+                Object::Enum(f) => Rc::ptr_eq(&e.variants, &f.variants),
+                _ => false,
+            },
+            Object::Variant(v) => match other {
+                Object::Variant(w) => {
+                    Rc::ptr_eq(&v.of.variants, &w.of.variants)
+                        && v.index == w.index
+                        && v.values == w.values
+                }
+                _ => false,
+            },
+            Object::Record(r) => match other {
+                Object::Record(s) => Rc::ptr_eq(&r.fields, &s.fields) && r.values == s.values,
+                _ => false,
+            },
+            Object::Generator(g) => match other {
+                Object::Generator(h) => Rc::ptr_eq(g, h),
+                _ => false,
+            },
+            Object::Promise(p) => match other {
+                Object::Promise(q) => Rc::ptr_eq(p, q),
                 _ => false,
             },
-            Object::Nil => match other {
-                Object::Nil => true,
+            Object::Nil => matches!(other, Object::Nil),
+            // every lookup of a method binds it anew
+            Object::Function(f) => match other {
+                Object::Function(g) => Rc::ptr_eq(&f.fun, &g.fun),
                 _ => false,
             },
-            Object::Function(_) => false,
-            Object::Return(_) => false,
         }
     }
 }
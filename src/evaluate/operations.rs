@@ -1,7 +1,24 @@
 use super::*;
 
+/// Operators try an instance's `__add` and the like first.
+pub fn overload(
+    name: &str,
+    target: &Object,
+    args: Vec<Object>,
+) -> Option<Result<Object, RuntimeError>> {
+    let method = target.get_object()?.method(name)?;
+    Some(method.call(args))
+}
+
 impl EvalBinOp for EqualityOp {
     fn evaluate(&self, left: Object, right: Object) -> Result<Object, RuntimeError> {
+        if let Some(res) = overload("__eq", &left, vec![right.clone()]) {
+            let eq = statements::get_do_or_not(res?);
+            return Ok(Object::Boolean(match self {
+                EqualityOp::NotEquals => !eq,
+                EqualityOp::EqualEquals => eq,
+            }));
+        }
         match self {
             EqualityOp::NotEquals => Ok(Object::Boolean(left != right)),
             EqualityOp::EqualEquals => Ok(Object::Boolean(left == right)),
@@ -11,12 +28,21 @@ impl EvalBinOp for EqualityOp {
 
 impl EvalBinOp for ComparisionOp {
     fn evaluate(&self, left: Object, right: Object) -> Result<Object, RuntimeError> {
-        let left = left.get_number().ok_or(RuntimeError {
-            err: "Operands must be numbers.".into(),
-        })?;
-        let right = right.get_number().ok_or(RuntimeError {
-            err: "Operands must be numbers.".into(),
-        })?;
+        let name = match self {
+            ComparisionOp::Less => "__lt",
+            ComparisionOp::LessEqual => "__le",
+            ComparisionOp::Greater => "__gt",
+            ComparisionOp::GreaterEqual => "__ge",
+        };
+        if let Some(res) = overload(name, &left, vec![right.clone()]) {
+            return res;
+        }
+        let left = left
+            .get_number()
+            .ok_or(RuntimeError::new("Operands must be numbers.".into()))?;
+        let right = right
+            .get_number()
+            .ok_or(RuntimeError::new("Operands must be numbers.".into()))?;
 
         let val = match self {
             ComparisionOp::Less => left < right,
@@ -30,9 +56,14 @@ impl EvalBinOp for ComparisionOp {
 
 impl EvalBinOp for TermOp {
     fn evaluate(&self, left: Object, right: Object) -> Result<Object, RuntimeError> {
-        let err = RuntimeError {
-            err: "Operands must be two numbers or two strings.".into(),
+        let name = match self {
+            TermOp::Plus => "__add",
+            TermOp::Minus => "__sub",
         };
+        if let Some(res) = overload(name, &left, vec![right.clone()]) {
+            return res;
+        }
+        let err = RuntimeError::new("Operands must be two numbers or two strings.".into());
         if let Some(left) = left.get_number() {
             let right = right.get_number().ok_or(err)?;
 
@@ -56,28 +87,55 @@ impl EvalBinOp for TermOp {
 
 impl EvalBinOp for FactorOp {
     fn evaluate(&self, left: Object, right: Object) -> Result<Object, RuntimeError> {
-        let left = left.get_number().ok_or(RuntimeError {
-            err: "Operands must be numbers.".into(),
-        })?;
-        let right = right.get_number().ok_or(RuntimeError {
-            err: "Operands must be numbers.".into(),
-        })?;
+        let name = match self {
+            FactorOp::Mul => "__mul",
+            FactorOp::Div => "__div",
+            FactorOp::Mod => "__mod",
+            FactorOp::IntDiv => "__intdiv",
+        };
+        if let Some(res) = overload(name, &left, vec![right.clone()]) {
+            return res;
+        }
+        let left = left
+            .get_number()
+            .ok_or(RuntimeError::new("Operands must be numbers.".into()))?;
+        let right = right
+            .get_number()
+            .ok_or(RuntimeError::new("Operands must be numbers.".into()))?;
 
         let val = match self {
             FactorOp::Mul => left * right,
             FactorOp::Div => left / right,
+            FactorOp::Mod => left - right * (left / right).floor(),
+            FactorOp::IntDiv => (left / right).floor(),
         };
         Ok(Object::Number(val))
     }
 }
 
+pub fn pow(base: Object, exp: Object) -> Result<Object, RuntimeError> {
+    if let Some(res) = overload("__pow", &base, vec![exp.clone()]) {
+        return res;
+    }
+    let base = base
+        .get_number()
+        .ok_or(RuntimeError::new("Operands must be numbers.".into()))?;
+    let exp = exp
+        .get_number()
+        .ok_or(RuntimeError::new("Operands must be numbers.".into()))?;
+    Ok(Object::Number(base.powf(exp)))
+}
+
 impl EvalUnOp for UnaryOp {
     fn evaluate(&self, exp: Object) -> Result<Object, RuntimeError> {
         match self {
             UnaryOp::Minus => {
-                let n = exp.get_number().ok_or(RuntimeError {
-                    err: "Operand must be a number".into(),
-                })?;
+                if let Some(res) = overload("__neg", &exp, vec![]) {
+                    return res;
+                }
+                let n = exp
+                    .get_number()
+                    .ok_or(RuntimeError::new("Operand must be a number".into()))?;
                 Ok(Object::Number(-n))
             }
             UnaryOp::Bang => {
@@ -92,3 +150,24 @@ impl EvalUnOp for UnaryOp {
         }
     }
 }
+
+pub fn index(target: &Object, i: &Object) -> Result<Object, RuntimeError> {
+    match target {
+        Object::List(list) => list::get(list, i),
+        Object::Map(map) => map::get(map, i),
+        _ => overload("__index", target, vec![i.clone()]).unwrap_or(Err(RuntimeError::new(
+            "Can only index lists and maps.".into(),
+        ))),
+    }
+}
+
+pub fn set_index(target: &Object, i: &Object, value: Object) -> Result<Object, RuntimeError> {
+    match target {
+        Object::List(list) => list::set(list, i, value),
+        Object::Map(map) => map::set(map, i, value),
+        _ => match overload("__setindex", target, vec![i.clone(), value.clone()]) {
+            Some(res) => res.map(|_| value),
+            None => Err(RuntimeError::new("Can only index lists and maps.".into())),
+        },
+    }
+}
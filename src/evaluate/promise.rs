@@ -0,0 +1,195 @@
+use super::*;
+use crate::native_function::{args, native};
+use generator::{GeneratorState, Step};
+
+pub type Promise = Rc<RefCell<PromiseState>>;
+
+pub struct PromiseState {
+    settled: Settled,
+    callbacks: Vec<Callback>,
+    /// Whether anything waited, to report rejections nothing handles.
+    handled: bool,
+}
+
+#[derive(Clone)]
+enum Settled {
+    Pending,
+    Fulfilled(Object),
+    Rejected(RuntimeError),
+}
+
+type Callback = Box<dyn FnOnce(Result<Object, RuntimeError>)>;
+
+thread_local! {
+    static UNHANDLED: RefCell<Vec<Promise>> = const { RefCell::new(vec![]) };
+}
+
+pub fn new_promise() -> Promise {
+    Rc::new(RefCell::new(PromiseState {
+        settled: Settled::Pending,
+        callbacks: vec![],
+        handled: false,
+    }))
+}
+
+pub fn from(value: Object) -> Promise {
+    if let Object::Promise(p) = value {
+        return p;
+    }
+    let p = new_promise();
+    settle(&p, Ok(value));
+    p
+}
+
+/// Settles `promise` unless it already settled.
+pub fn settle(promise: &Promise, res: Result<Object, RuntimeError>) {
+    let mut state = promise.borrow_mut();
+    if !matches!(state.settled, Settled::Pending) {
+        return;
+    }
+    state.settled = match &res {
+        Ok(v) => Settled::Fulfilled(v.clone()),
+        Err(e) => Settled::Rejected(e.clone()),
+    };
+    if res.is_err() && !state.handled {
+        UNHANDLED.with_borrow_mut(|u| u.push(promise.clone()));
+    }
+    for callback in std::mem::take(&mut state.callbacks) {
+        let res = res.clone();
+        event_loop::queue_microtask(Box::new(move || {
+            callback(res);
+            Ok(())
+        }));
+    }
+}
+
+/// Adopts the state of `value` when it is a promise itself.
+pub fn resolve(promise: &Promise, value: Object) {
+    match value {
+        Object::Promise(other) => {
+            let promise = promise.clone();
+            on_settle(&other, move |res| settle(&promise, res));
+        }
+        v => settle(promise, Ok(v)),
+    }
+}
+
+pub fn on_settle(promise: &Promise, callback: impl FnOnce(Result<Object, RuntimeError>) + 'static) {
+    let mut state = promise.borrow_mut();
+    state.handled = true;
+    let res = match &state.settled {
+        Settled::Pending => return state.callbacks.push(Box::new(callback)),
+        Settled::Fulfilled(v) => Ok(v.clone()),
+        Settled::Rejected(e) => Err(e.clone()),
+    };
+    event_loop::queue_microtask(Box::new(move || {
+        callback(res);
+        Ok(())
+    }));
+}
+
+pub fn check_unhandled() -> Result<(), RuntimeError> {
+    let rejected = UNHANDLED.with_borrow_mut(std::mem::take);
+    for p in rejected {
+        let state = p.borrow();
+        if let (Settled::Rejected(e), false) = (&state.settled, state.handled) {
+            return Err(e.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Runs up to the first `await` right away.
+pub fn run_async(name: &str, body: &Block, env: Rc<RefCell<Env>>) -> Object {
+    let body = Rc::new(RefCell::new(GeneratorState::new(name, body, env)));
+    let promise = new_promise();
+    step(body, promise.clone(), None);
+    Object::Promise(promise)
+}
+
+fn step(
+    body: Rc<RefCell<GeneratorState>>,
+    promise: Promise,
+    awaited: Option<Result<Object, RuntimeError>>,
+) {
+    let res = body.borrow_mut().resume(awaited);
+    match res {
+        Ok(Step::Await(v)) => {
+            on_settle(&from(v), move |res| step(body, promise, Some(res)));
+        }
+        Ok(Step::Done(v)) => resolve(&promise, v),
+        Ok(Step::Yield(_)) => unreachable!("the resolver only allows yields in generators"),
+        Err(e) => settle(&promise, Err(e)),
+    }
+}
+
+pub fn constructor(passed: Vec<Object>) -> Result<Object, RuntimeError> {
+    let [executor] = args(passed)?;
+    let Object::Function(executor) = executor else {
+        return Err(RuntimeError::new(
+            "Promise executor must be a function.".into(),
+        ));
+    };
+    let promise = new_promise();
+    let (p, q) = (promise.clone(), promise.clone());
+    let resolve_fn = native("resolve", &["value"], move |passed| {
+        let [value] = args(passed)?;
+        resolve(&p, value);
+        Ok(Object::Nil)
+    });
+    let reject_fn = native("reject", &["reason"], move |passed| {
+        let [reason] = args(passed)?;
+        settle(&q, Err(RuntimeError::throw(reason)));
+        Ok(Object::Nil)
+    });
+    if let Err(e) = executor.call(vec![resolve_fn, reject_fn]) {
+        settle(&promise, Err(e));
+    }
+    Ok(Object::Promise(promise))
+}
+
+pub fn method(promise: &Promise, name: &str) -> Result<Object, RuntimeError> {
+    let promise = promise.clone();
+    let fun = match name {
+        "then" => native("then", &["onFulfilled", "onRejected"], move |passed| {
+            let len = passed.len();
+            let mut passed = passed.into_iter();
+            let (Some(on_fulfilled), on_rejected, None) =
+                (passed.next(), passed.next(), passed.next())
+            else {
+                return Err(RuntimeError::new(format!(
+                    "Expect 1 or 2 arguments but got {len}."
+                )));
+            };
+            Ok(then(&promise, Some(on_fulfilled), on_rejected))
+        }),
+        "catch" => native("catch", &["onRejected"], move |passed| {
+            let [on_rejected] = args(passed)?;
+            Ok(then(&promise, None, Some(on_rejected)))
+        }),
+        _ => return Err(RuntimeError::new(format!("Undefined property '{name}'."))),
+    };
+    Ok(fun)
+}
+
+fn then(promise: &Promise, on_fulfilled: Option<Object>, on_rejected: Option<Object>) -> Object {
+    let next = new_promise();
+    let res = next.clone();
+    on_settle(promise, move |outcome| {
+        let (handler, arg) = match outcome {
+            Ok(v) => (on_fulfilled, Ok(v)),
+            Err(e) => (on_rejected, Err(e)),
+        };
+        match (handler, arg) {
+            (Some(Object::Function(f)), arg) => {
+                let arg = arg.unwrap_or_else(|e| e.value());
+                match f.call(vec![arg]) {
+                    Ok(v) => resolve(&next, v),
+                    Err(e) => settle(&next, Err(e)),
+                }
+            }
+            (_, arg) => settle(&next, arg),
+        }
+    });
+    Object::Promise(res)
+}
@@ -0,0 +1,59 @@
+use super::*;
+use crate::native_function::{args, arity, native};
+
+/// Fields are read-only; `with` makes a changed copy.
+#[derive(Clone)]
+pub struct Record {
+    pub name: String,
+    pub fields: Rc<Vec<String>>,
+    pub values: Vec<Object>,
+}
+
+pub fn constructor(name: &str, fields: Vec<String>) -> Object {
+    let params: Vec<_> = fields.iter().map(String::as_str).collect();
+    let record = name.to_string();
+    let shape = Rc::new(fields.clone());
+    native(name, &params, move |passed| {
+        arity(shape.len(), passed.len())?;
+        Ok(Object::Record(Record {
+            name: record.clone(),
+            fields: shape.clone(),
+            values: passed,
+        }))
+    })
+}
+
+impl Record {
+    pub fn get(&self, name: &str) -> Result<Object, RuntimeError> {
+        if let Some(i) = self.fields.iter().position(|f| f == name) {
+            return Ok(self.values[i].clone());
+        }
+        if name != "with" {
+            return Err(RuntimeError::new(format!("Undefined property '{name}'.")));
+        }
+
+        let record = self.clone();
+        Ok(native("with", &["changes"], move |passed| {
+            let [changes] = args(passed)?;
+            let Object::Map(changes) = changes else {
+                return Err(RuntimeError::new(
+                    "Expect a map of field names to new values.".into(),
+                ));
+            };
+            let mut copy = record.clone();
+            for (k, v) in changes.borrow().iter() {
+                let field = Object::from(k);
+                let i = record
+                    .fields
+                    .iter()
+                    .position(|f| Object::String(f.clone()) == field)
+                    .ok_or(RuntimeError::new(format!(
+                        "Record '{}' has no field '{field}'.",
+                        record.name
+                    )))?;
+                copy.values[i] = v.clone();
+            }
+            Ok(Object::Record(copy))
+        }))
+    }
+}
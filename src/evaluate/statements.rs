@@ -1,26 +1,36 @@
 use super::*;
 
-impl Eval for Statement {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for Statement {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         match self {
-            Statement::ExprStmt(expression) => expression.evaluate(env),
-            Statement::ForStmt(for_stmt) => for_stmt.evaluate(env),
-            Statement::IfStmt(if_stmt) => if_stmt.evaluate(env),
-            Statement::RtrnStmt(rtrn_stmt) => rtrn_stmt.evaluate(env),
-            Statement::WhileStmt(while_stmt) => while_stmt.evaluate(env),
-            Statement::PrntStmt(prnt_stmt) => prnt_stmt.evaluate(env),
-            Statement::Block(block) => block.evaluate(env),
+            Statement::BrkStmt(_) => Ok(Completion::Break),
+            Statement::CntStmt(_) => Ok(Completion::Continue),
+            Statement::ExprStmt(expression) => expression.execute(env),
+            Statement::ForStmt(for_stmt) => for_stmt.execute(env),
+            Statement::ForInStmt(for_in_stmt) => for_in_stmt.execute(env),
+            Statement::IfStmt(if_stmt) => if_stmt.execute(env),
+            Statement::MatchStmt(match_stmt) => match_stmt.execute(env),
+            Statement::RtrnStmt(rtrn_stmt) => rtrn_stmt.execute(env),
+            Statement::ThrwStmt(thrw_stmt) => thrw_stmt.execute(env),
+            Statement::TryStmt(try_stmt) => try_stmt.execute(env),
+            Statement::WhileStmt(while_stmt) => while_stmt.execute(env),
+            Statement::PrntStmt(prnt_stmt) => prnt_stmt.execute(env),
+            Statement::YieldStmt(_) => {
+                unreachable!("generators run the statements that yield themselves")
+            }
+            Statement::Block(block) => block.execute(env),
         }
     }
 }
 
-impl Eval for ExprStmt {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
-        self.0.evaluate(env)
+impl Exec for ExprStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        self.0.evaluate(env)?;
+        Ok(Completion::Normal)
     }
 }
 
-fn get_do_or_not(v: Object) -> bool {
+pub(super) fn get_do_or_not(v: Object) -> bool {
     match v {
         Object::Number(n) => n != 0.0,
         Object::String(s) => !s.is_empty(),
@@ -30,23 +40,120 @@ fn get_do_or_not(v: Object) -> bool {
     }
 }
 
-impl Eval for IfStmt {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for IfStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         let v = self.pred.evaluate(env.clone())?;
         if get_do_or_not(v) {
-            self.if_stmt.evaluate(env)
+            self.if_stmt.execute(env)
         } else if let Some(el) = &self.else_stmt {
-            el.evaluate(env)
+            el.execute(env)
         } else {
-            Ok(Object::Nil)
+            Ok(Completion::Normal)
+        }
+    }
+}
+
+impl Exec for MatchStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        match self.select(env)? {
+            Some((body, env)) => body.execute(env),
+            None => Ok(Completion::Normal),
+        }
+    }
+}
+
+type SelectedArm<'a> = (&'a Statement, Rc<RefCell<Env>>);
+
+impl MatchStmt {
+    pub(super) fn select(
+        &self,
+        env: Rc<RefCell<Env>>,
+    ) -> Result<Option<SelectedArm<'_>>, RuntimeError> {
+        let subject = self.subject.evaluate(env.clone())?;
+        for arm in &self.arms {
+            let env = Env::new_box_it(Some(env.clone()));
+            let matched = match &arm.patterns {
+                Some(patterns) => {
+                    let mut matched = false;
+                    for p in patterns {
+                        if p.matches(&subject, env.clone())? {
+                            matched = true;
+                            break;
+                        }
+                    }
+                    matched
+                }
+                None => true,
+            };
+            if matched {
+                return Ok(Some((&arm.body, env)));
+            }
         }
+        Ok(None)
     }
 }
 
-impl Eval for ForStmt {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Pattern {
+    fn matches(&self, value: &Object, env: Rc<RefCell<Env>>) -> Result<bool, RuntimeError> {
+        match self {
+            Pattern::Value(p) => Ok(p.evaluate(env)? == *value),
+            Pattern::Range(lo, hi) => {
+                Ok(matches!(value, Object::Number(n) if (lo..hi).contains(&n)))
+            }
+            Pattern::Class(name, fields) => {
+                if let Object::Variant(v) = value {
+                    if v.of.position(name)? != v.index {
+                        return Ok(false);
+                    }
+                    for field in fields {
+                        let f = v.get(field).ok_or(RuntimeError::new(format!(
+                            "Variant '{name}' has no field '{field}'."
+                        )))?;
+                        env.borrow_mut().values.insert(field.clone(), f);
+                    }
+                    return Ok(true);
+                }
+                if let Object::Record(r) = value {
+                    if r.name != *name {
+                        return Ok(false);
+                    }
+                    for field in fields {
+                        let Some(i) = r.fields.iter().position(|f| f == field) else {
+                            return Ok(false);
+                        };
+                        env.borrow_mut()
+                            .values
+                            .insert(field.clone(), r.values[i].clone());
+                    }
+                    return Ok(true);
+                }
+                let class = environment::find_id(name, Some(env.clone()))
+                    .and_then(|e| e.borrow().values.get(name)?.get_function()?.class.clone());
+                let Some(obj) = value.get_object().filter(|o| {
+                    o.class
+                        .as_ref()
+                        .zip(class.as_ref())
+                        .is_some_and(|(def, class)| def.is_a(class))
+                }) else {
+                    return Ok(false);
+                };
+                let values = obj.env.borrow().values.clone();
+                for field in fields {
+                    let Some(v) = values.get(field) else {
+                        return Ok(false);
+                    };
+                    env.borrow_mut().values.insert(field.clone(), v.clone());
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl Exec for ForStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         let env = Env::new_box_it(Some(env));
-        let res = self.first_dec.evaluate(env.clone())?;
+        self.first_dec.execute(env.clone())?;
         loop {
             if let Some(pred) = &self.scnd_expr {
                 let p = pred.evaluate(env.clone())?;
@@ -54,69 +161,175 @@ impl Eval for ForStmt {
                     break;
                 }
             }
-            let v = self.body.evaluate(env.clone())?;
-            match &v {
-                Object::Return(_) => return Ok(v),
-                _ => {}
+            match self.body.execute(env.clone())? {
+                Completion::Break => break,
+                Completion::Normal | Completion::Continue => {}
+                c => return Ok(c),
             }
             if let Some(post) = &self.thrd_expr {
                 post.evaluate(env.clone())?;
             }
         }
-        Ok(res)
+        Ok(Completion::Normal)
+    }
+}
+
+impl Exec for ForInStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        for item in self.items(env.clone())? {
+            // a fresh scope each time, so closures made in the body keep
+            // the item they saw
+            let scope = Env::new_box_it(Some(env.clone()));
+            self.binding.bind(item?, &scope)?;
+            match self.body.execute(scope)? {
+                Completion::Break => break,
+                Completion::Normal | Completion::Continue => {}
+                c => return Ok(c),
+            }
+        }
+        Ok(Completion::Normal)
+    }
+}
+
+impl ForInStmt {
+    pub(super) fn items(&self, env: Rc<RefCell<Env>>) -> Result<Items, RuntimeError> {
+        let iterable = self.iterable.evaluate(env.clone())?;
+        match &self.end {
+            Some(end) => range(iterable, end.evaluate(env)?),
+            None => iterate(iterable),
+        }
     }
 }
 
-impl Eval for ForDec {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+pub(super) type Items = Box<dyn Iterator<Item = Result<Object, RuntimeError>>>;
+
+fn range(lo: Object, hi: Object) -> Result<Items, RuntimeError> {
+    let (Some(lo), Some(hi)) = (lo.get_number(), hi.get_number()) else {
+        return Err(RuntimeError::new("Range bounds must be numbers.".into()));
+    };
+    let count = (hi - lo).ceil().max(0.0) as usize;
+    Ok(Box::new(
+        (0..count).map(move |i| Ok(Object::Number(lo + i as f64))),
+    ))
+}
+
+/// Lists and maps are copied, so the body can change them.
+fn iterate(iterable: Object) -> Result<Items, RuntimeError> {
+    let items: Vec<Object> = match &iterable {
+        Object::List(list) => list.borrow().clone(),
+        Object::Map(map) => map.borrow().keys().map(Object::from).collect(),
+        Object::String(s) => s.chars().map(|c| Object::String(c.into())).collect(),
+        Object::Generator(g) => {
+            let g = g.clone();
+            return Ok(Box::new(std::iter::from_fn(move || {
+                generator::next(&g).transpose()
+            })));
+        }
+        Object::Object(cls) => {
+            let iter = match cls.method("iterator") {
+                Some(iterator) => iterator.call(vec![])?,
+                None => iterable.clone(),
+            };
+            let methods = iter
+                .get_object()
+                .and_then(|i| Some((i.method("hasNext")?, i.method("next")?)));
+            let Some((has_next, next)) = methods else {
+                return Err(RuntimeError::new(
+                    "Iterators must have 'hasNext' and 'next' methods.".into(),
+                ));
+            };
+            return Ok(Box::new(std::iter::from_fn(move || {
+                let more = match has_next.call(vec![]) {
+                    Ok(more) => get_do_or_not(more),
+                    Err(e) => return Some(Err(e)),
+                };
+                more.then(|| next.call(vec![]))
+            })));
+        }
+        _ => {
+            return Err(RuntimeError::new(
+                "Can only iterate over lists, maps, strings and iterators.".into(),
+            ))
+        }
+    };
+    Ok(Box::new(items.into_iter().map(Ok)))
+}
+
+impl Exec for ForDec {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         match self {
-            ForDec::VarDecl(var_decl) => var_decl.evaluate(env),
-            ForDec::ExprStmt(expr_stmt) => expr_stmt.evaluate(env),
-            ForDec::Nil => Ok(Object::Nil),
+            ForDec::VarDecl(var_decl) => var_decl.execute(env),
+            ForDec::ExprStmt(expr_stmt) => expr_stmt.execute(env),
+            ForDec::Nil => Ok(Completion::Normal),
         }
     }
 }
 
-impl Eval for WhileStmt {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for WhileStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         while get_do_or_not(self.pred.evaluate(env.clone())?) {
-            let v = self.stmt.evaluate(env.clone())?;
-            match &v {
-                Object::Return(_) => return Ok(v),
-                _ => {}
+            match self.stmt.execute(env.clone())? {
+                Completion::Break => break,
+                Completion::Normal | Completion::Continue => {}
+                c => return Ok(c),
             }
         }
-        Ok(Object::Nil)
+        Ok(Completion::Normal)
     }
 }
 
-impl Eval for PrntStmt {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for PrntStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         let val = self.0.evaluate(env)?;
-        println!("{val}");
-        Ok(val)
+        println!("{}", val.stringify()?);
+        Ok(Completion::Normal)
     }
 }
 
-impl Eval for Block {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for Block {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         let env = Env::new_box_it(Some(env));
         for d in &self.0 {
-            let v = d.evaluate(env.clone())?;
-            match &v {
-                Object::Return(_) => return Ok(v),
-                _ => {}
+            match d.execute(env.clone())? {
+                Completion::Normal => {}
+                c => return Ok(c),
             }
         }
-        Ok(Object::Nil)
+        Ok(Completion::Normal)
     }
 }
 
-impl Eval for RtrnStmt {
-    fn evaluate(&self, env: Rc<RefCell<Env>>) -> Result<Object, RuntimeError> {
+impl Exec for RtrnStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
         Ok(match &self.0 {
-            Some(e) => Object::Return(Box::new(e.evaluate(env)?)),
-            None => Object::Return(Box::new(Object::Nil)),
+            Some(e) => Completion::Return(e.evaluate(env)?),
+            None => Completion::Return(Object::Nil),
         })
     }
 }
+
+impl Exec for ThrwStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        let v = self.0.evaluate(env)?;
+        Err(RuntimeError::throw(v).at(self.0.line))
+    }
+}
+
+impl Exec for TryStmt {
+    fn execute(&self, env: Rc<RefCell<Env>>) -> Result<Completion, RuntimeError> {
+        let mut res = self.body.execute(env.clone());
+        if let (Err(e), Some((name, block))) = (&res, &self.catch) {
+            let env = Env::new_box_it(Some(env.clone()));
+            env.borrow_mut().values.insert(name.clone(), e.value());
+            res = block.execute(env);
+        }
+        // a finally block that jumps or throws wins over whatever came before
+        if let Some(block) = &self.finally {
+            match block.execute(env)? {
+                Completion::Normal => {}
+                c => return Ok(c),
+            }
+        }
+        res
+    }
+}
@@ -2,23 +2,25 @@ use std::env;
 use std::fs;
 use std::io::{self, Write};
 use std::process::exit;
-use std::sync::Arc;
 
-use ast::Block;
 use ast::Expression;
 use evaluate::environment::Env;
+use evaluate::event_loop;
+use evaluate::module;
 use evaluate::Eval;
-use evaluate::ExFn;
-use evaluate::Object;
+use evaluate::Exec;
 use evaluate::RuntimeError;
 use parse::Parser;
+use resolve::{Resolve, Resolver};
 use scan::Scanner;
+use token::TokenType;
 
 mod ast;
 mod display;
 mod evaluate;
 mod native_function;
 mod parse;
+mod resolve;
 mod scan;
 mod token;
 
@@ -33,7 +35,7 @@ fn main() -> std::io::Result<()> {
     let filename = &args[2];
 
     let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-        writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+        eprintln!("Failed to read file {}", filename);
         String::new()
     });
 
@@ -52,22 +54,22 @@ fn main() -> std::io::Result<()> {
         "run" => {
             let scanner = tokenize(file_contents, false)?;
             let parser = parse(&scanner, false)?;
+            let program = parser.program.unwrap();
+            resolve(&program)?;
             let env = Env::new_box_it(None);
-
-            env.borrow_mut().values.insert(
-                "clock".into(),
-                Object::Function(ExFn {
-                    body: Block(vec![]),
-                    name: "clock".into(),
-                    params: vec![],
-                    fun: Arc::new(|_, _, _, _| Ok(native_function::clock())),
-                    env: env.clone(),
-                }),
-            );
-
-            for d in parser.program.unwrap().declarations {
-                catch_err(d.evaluate(env.clone()));
+            native_function::define_globals(&env);
+            // timers fire without waiting, which keeps runs deterministic
+            if args[3..].iter().any(|a| a == "--virtual-clock") {
+                event_loop::use_virtual_clock();
             }
+
+            let path = fs::canonicalize(filename).unwrap_or_else(|_| filename.into());
+            module::run_as(path, || {
+                for d in program.declarations {
+                    catch_err(d.execute(env.clone()));
+                }
+                catch_err(event_loop::run());
+            });
         }
         _ => {
             writeln!(io::stderr(), "Unknown command: {}", command)?;
@@ -82,6 +84,9 @@ fn tokenize(file_contents: String, debug: bool) -> std::io::Result<Scanner> {
     for ele in scanner.errors.iter() {
         writeln!(io::stderr(), "[line {}] Error: {}", ele.line, ele.tok)?;
     }
+    if scanner.errors.is_empty() {
+        parse::settle_comments(&mut scanner);
+    }
     if debug {
         for ele in scanner.tokens.iter() {
             writeln!(io::stdout(), "{ele}")?;
@@ -93,12 +98,13 @@ fn tokenize(file_contents: String, debug: bool) -> std::io::Result<Scanner> {
     Ok(scanner)
 }
 
-fn parse<'a>(scanner: &'a Scanner, debug: bool) -> std::io::Result<Parser> {
+fn parse(scanner: &Scanner, debug: bool) -> std::io::Result<Parser<'_>> {
     let mut parser = Parser::new(&scanner.tokens);
     if let Err(err) = parser.parse() {
         match Expression::parse(&scanner.tokens) {
-            Ok((e, _)) => write!(io::stdout(), "{e}")?,
-            Err(_) => {
+            // a file that is one expression, like in the parsing chapter
+            Ok((e, rem)) if rem[0].token_type == TokenType::Eof => write!(io::stdout(), "{e}")?,
+            _ => {
                 writeln!(
                     io::stderr(),
                     "[line {}] Error at '{}': {}",
@@ -117,7 +123,32 @@ fn parse<'a>(scanner: &'a Scanner, debug: bool) -> std::io::Result<Parser> {
     Ok(parser)
 }
 
-fn evaluate<'a>(scanner: &'a Scanner) -> std::io::Result<()> {
+fn resolve(program: &ast::Program) -> std::io::Result<()> {
+    let mut resolver = Resolver::new();
+    let res = program.resolve(&mut resolver);
+    for w in &resolver.warnings {
+        writeln!(
+            io::stderr(),
+            "[line {}] Warning at '{}': {}",
+            w.tok.line,
+            w.tok.lexeme,
+            w.err
+        )?;
+    }
+    if let Err(err) = res {
+        writeln!(
+            io::stderr(),
+            "[line {}] Error at '{}': {}",
+            err.tok.line,
+            err.tok.lexeme,
+            err.err
+        )?;
+        exit(65);
+    }
+    Ok(())
+}
+
+fn evaluate(scanner: &Scanner) -> std::io::Result<()> {
     let expr = Expression::parse(&scanner.tokens);
     match expr {
         Ok((ex, _)) => {
@@ -134,11 +165,14 @@ fn evaluate<'a>(scanner: &'a Scanner) -> std::io::Result<()> {
     }
 }
 
-fn catch_err(val: Result<Object, RuntimeError>) -> Object {
+fn catch_err<T>(val: Result<T, RuntimeError>) -> T {
     match val {
         Ok(v) => v,
         Err(e) => {
-            write!(io::stderr(), "{}", e.err).unwrap();
+            eprint!("{}", e.err);
+            if let Some(line) = e.line {
+                eprint!("\n[line {line}]");
+            }
             exit(70)
         }
     }
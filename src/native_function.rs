@@ -1,8 +1,111 @@
-use std::time::{self, UNIX_EPOCH};
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    rc::Rc,
+    time::{self, UNIX_EPOCH},
+};
 
-use crate::evaluate::Object;
+use crate::ast::Block;
+use crate::evaluate::{
+    environment::Env, error_class, event_loop, promise, ExFn, Object, RuntimeError,
+};
+
+pub fn define_globals(env: &Rc<RefCell<Env>>) {
+    let mut env = env.borrow_mut();
+    env.values.insert("Error".into(), error_class());
+    let mut define = |name: &str, params: &[&str], fun: Native| {
+        env.values.insert(name.into(), native(name, params, fun));
+    };
+    define("clock", &[], |_| Ok(clock()));
+    define("str", &["value"], |passed| {
+        let [value] = args(passed)?;
+        Ok(Object::String(value.stringify()?))
+    });
+    define("setTimeout", &["callback", "ms"], |passed| {
+        timer(passed, false)
+    });
+    define("setInterval", &["callback", "ms"], |passed| {
+        timer(passed, true)
+    });
+    define("clearTimeout", &["id"], clear_timer);
+    define("clearInterval", &["id"], clear_timer);
+    define("sleep", &["ms"], |passed| {
+        let [ms] = args(passed)?;
+        let Object::Number(ms) = ms else {
+            return Err(RuntimeError::new("Delay must be a number.".into()));
+        };
+        let p = promise::new_promise();
+        let q = p.clone();
+        event_loop::set_timer(
+            Rc::new(move || {
+                promise::settle(&q, Ok(Object::Nil));
+                Ok(())
+            }),
+            ms,
+            false,
+        );
+        Ok(Object::Promise(p))
+    });
+    define("Promise", &["executor"], promise::constructor);
+}
+
+type Native = fn(Vec<Object>) -> Result<Object, RuntimeError>;
+
+pub fn native(
+    name: &str,
+    params: &[&str],
+    fun: impl Fn(Vec<Object>) -> Result<Object, RuntimeError> + 'static,
+) -> Object {
+    Object::Function(ExFn {
+        body: Block(vec![]),
+        name: name.into(),
+        params: params.iter().map(|p| p.to_string()).collect(),
+        fun: Rc::new(move |passed, _, _, _| fun(passed)),
+        class: None,
+        env: Env::new_box_it(None),
+    })
+}
+
+fn timer(passed: Vec<Object>, repeat: bool) -> Result<Object, RuntimeError> {
+    let [callback, ms] = args(passed)?;
+    let (Object::Function(callback), Object::Number(ms)) = (callback, ms) else {
+        return Err(RuntimeError::new(
+            "Expect a function and a delay in milliseconds.".into(),
+        ));
+    };
+    let task = Rc::new(move || callback.call(vec![]).map(|_| ()));
+    let id = event_loop::set_timer(task, ms, repeat);
+    Ok(Object::Number(id as f64))
+}
+
+fn clear_timer(passed: Vec<Object>) -> Result<Object, RuntimeError> {
+    let [id] = args(passed)?;
+    if let Object::Number(id) = id {
+        event_loop::clear_timer(id as u64);
+    }
+    Ok(Object::Nil)
+}
+
+pub fn args<const N: usize>(passed: Vec<Object>) -> Result<[Object; N], RuntimeError> {
+    let len = passed.len();
+    passed.try_into().map_err(|_| arity_error(N, len))
+}
+
+pub fn arity(expect: usize, got: usize) -> Result<(), RuntimeError> {
+    if expect == got {
+        return Ok(());
+    }
+    Err(arity_error(expect, got))
+}
+
+pub fn arity_error(expect: impl Display, got: usize) -> RuntimeError {
+    RuntimeError::new(format!("Expect {expect} arguments but got {got}."))
+}
 
 pub fn clock() -> Object {
+    if event_loop::is_virtual() {
+        return Object::Number(event_loop::now() / 1000.0);
+    }
     let res = time::SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
@@ -2,10 +2,18 @@ use super::*;
 
 impl Declaration {
     pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        Self::parse_cls(src)
-            .or_else(|_| Self::parse_fun(src))
-            .or_else(|_| Self::parse_var(src))
-            .or_else(|_| Self::parse_stmt(src))
+        one_of(
+            src,
+            &[
+                Self::parse_cls,
+                Self::parse_enum,
+                Self::parse_fun,
+                Self::parse_import,
+                Self::parse_record,
+                Self::parse_var,
+                Self::parse_stmt,
+            ],
+        )
     }
 
     fn parse_cls<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
@@ -13,11 +21,26 @@ impl Declaration {
         Ok((Declaration::ClassDecl(dec), rem))
     }
 
+    fn parse_enum<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (dec, rem) = EnumDecl::parse(src)?;
+        Ok((Declaration::EnumDecl(dec), rem))
+    }
+
     fn parse_fun<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (dec, rem) = FunDecl::parse(src)?;
         Ok((Declaration::FunDecl(dec), rem))
     }
 
+    fn parse_import<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (dec, rem) = ImportDecl::parse(src)?;
+        Ok((Declaration::ImportDecl(dec), rem))
+    }
+
+    fn parse_record<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (dec, rem) = RecordDecl::parse(src)?;
+        Ok((Declaration::RecordDecl(dec), rem))
+    }
+
     fn parse_var<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (dec, rem) = VarDecl::parse(src)?;
         Ok((Declaration::VarDecl(dec), rem))
@@ -41,9 +64,58 @@ impl ClassDecl {
         }
         let mut rem = match_tok(rem, TokenType::LeftBrace, "'{' before class body")?;
         let mut funcs = vec![];
-        while let Ok((func, r)) = Function::parse(rem) {
-            funcs.push(func);
-            rem = r;
+        let mut getters = vec![];
+        let mut setters = vec![];
+        let mut statics = vec![];
+        let mut fields = vec![];
+        loop {
+            if is_static(rem) {
+                if matches!(rem.get(2), Some(t) if t.token_type == TokenType::LeftParen) {
+                    let (func, r) = Function::parse(&rem[1..], FunKind::Plain)?;
+                    statics.push(func);
+                    rem = r;
+                } else {
+                    let (field, r) = ClassField::parse(&rem[1..])?;
+                    fields.push(field);
+                    rem = r;
+                }
+            } else if let Ok((func, r)) = Function::parse_getter(rem) {
+                getters.push(func);
+                rem = r;
+            } else if rem[0].lexeme == "set" && rem[1].token_type == TokenType::Identifier {
+                let (func, r) = Function::parse(&rem[1..], FunKind::Plain)?;
+                if !matches!(&func.params, Some(p) if p.rest.is_none() && !p.variadic) {
+                    return Err(ParseError {
+                        tok: &rem[1],
+                        err: "Setter must take exactly one parameter.".into(),
+                    });
+                }
+                setters.push(func);
+                rem = r;
+            } else if rem[0].token_type == TokenType::At {
+                let (decorators, r) = Function::parse_decorators(rem)?;
+                let is_static = is_static(r);
+                let (mut func, r) = match is_static {
+                    true => Function::parse(&r[1..], FunKind::Plain)?,
+                    false if is_async_method(r) => Function::parse(&r[1..], FunKind::Async)?,
+                    false => Function::parse(r, FunKind::Plain)?,
+                };
+                func.decorators = decorators;
+                match is_static {
+                    true => statics.push(func),
+                    false => funcs.push(func),
+                }
+                rem = r;
+            } else if is_async_method(rem) {
+                let (func, r) = Function::parse(&rem[1..], FunKind::Async)?;
+                funcs.push(func);
+                rem = r;
+            } else if let Ok((func, r)) = Function::parse(rem, FunKind::Plain) {
+                funcs.push(func);
+                rem = r;
+            } else {
+                break;
+            }
         }
         let rem = match_tok(rem, TokenType::RightBrace, "'}' after class body")?;
         Ok((
@@ -51,74 +123,373 @@ impl ClassDecl {
                 name: class_name,
                 super_class,
                 functions: funcs,
+                getters,
+                setters,
+                statics,
+                fields,
             },
             rem,
         ))
     }
 }
 
+impl ClassField {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (name, rem) = get_identifier(src)?;
+        let mut expr = None;
+        let rem = match match_tok(rem, TokenType::Equal, "'=' after field name") {
+            Ok(r) => {
+                let (e, r) = Expression::parse(r)?;
+                let _ = expr.insert(e);
+                r
+            }
+            Err(_) => rem,
+        };
+        let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+        Ok((ClassField { name, expr }, rem))
+    }
+}
+
+impl EnumDecl {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_word(src, "enum", "enum declaration")?;
+        let (name, rem) = get_identifier(rem)?;
+        let rem = match_tok(rem, TokenType::LeftBrace, "'{' before enum body")?;
+        let (variant, mut rem) = Self::parse_variant(rem)?;
+        let mut variants = vec![variant];
+        while let Ok(start) = match_tok(rem, TokenType::Comma, ",") {
+            let (variant, r) = Self::parse_variant(start)?;
+            if variants.iter().any(|(v, _)| *v == variant.0) {
+                return Err(ParseError {
+                    tok: &start[0],
+                    err: format!("Variant '{}' is already declared.", variant.0),
+                });
+            }
+            variants.push(variant);
+            rem = r;
+        }
+        let rem = match_tok(rem, TokenType::RightBrace, "'}' after enum body")?;
+        Ok((EnumDecl { name, variants }, rem))
+    }
+
+    fn parse_variant<'a>(src: &'a [Token]) -> Result<(VariantDecl, &'a [Token]), ParseError<'a>> {
+        let (name, rem) = get_identifier(src)?;
+        if rem[0].token_type != TokenType::LeftParen {
+            return Ok(((name, vec![]), rem));
+        }
+        let (fields, rem) = parse_fields(rem, "')' after variant fields")?;
+        Ok(((name, fields), rem))
+    }
+}
+
+impl RecordDecl {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_word(src, "record", "record declaration")?;
+        let (name, rem) = get_identifier(rem)?;
+        let (fields, rem) = parse_fields(rem, "')' after record fields")?;
+        let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+        Ok((RecordDecl { name, fields }, rem))
+    }
+}
+
+fn parse_fields<'a>(
+    src: &'a [Token],
+    closing: &str,
+) -> Result<(Vec<String>, &'a [Token]), ParseError<'a>> {
+    let mut rem = match_tok(src, TokenType::LeftParen, "'('")?;
+    let mut fields: Vec<String> = vec![];
+    if rem[0].token_type != TokenType::RightParen {
+        loop {
+            let (field, r) = get_identifier(rem)?;
+            if fields.contains(&field) {
+                return Err(ParseError {
+                    tok: &rem[0],
+                    err: format!("Field '{field}' is already declared."),
+                });
+            }
+            fields.push(field);
+            rem = r;
+            let Ok(r) = match_tok(rem, TokenType::Comma, ",") else {
+                break;
+            };
+            rem = r;
+        }
+    }
+    let rem = match_tok(rem, TokenType::RightParen, closing)?;
+    Ok((fields, rem))
+}
+
 impl FunDecl {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        let rem = match_tok(src, TokenType::Fun, "fun")?;
-        let (func, rem) = Function::parse(rem)?;
+        let (decorators, rem) = Function::parse_decorators(src)?;
+        let (kind, rem) = Function::parse_kind(rem)?;
+        let (mut func, rem) = Function::parse(rem, kind)?;
+        func.decorators = decorators;
         Ok((FunDecl(func), rem))
     }
 }
 
+impl ImportDecl {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        if let Ok(rem) = match_tok(src, TokenType::Import, "import") {
+            let (path, rem) = Self::parse_path(rem)?;
+            let rem = match_word(rem, "as", "'as' after module path")?;
+            let (name, rem) = get_identifier(rem)?;
+            let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+            let tok = src[0].clone();
+            let names = Imports::As(name);
+            return Ok((ImportDecl { tok, path, names }, rem));
+        }
+
+        let rem = match_word(src, "from", "from")?;
+        let (path, rem) = Self::parse_path(rem)?;
+        let rem = match_tok(rem, TokenType::Import, "'import' after module path")?;
+        let (name, mut rem) = get_identifier(rem)?;
+        let mut names = vec![name];
+        while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+            let (name, r) = get_identifier(r)?;
+            names.push(name);
+            rem = r;
+        }
+        let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+        let tok = src[0].clone();
+        let names = Imports::Names(names);
+        Ok((ImportDecl { tok, path, names }, rem))
+    }
+
+    fn parse_path<'a>(src: &'a [Token]) -> Result<(String, &'a [Token]), ParseError<'a>> {
+        match &src[0] {
+            Token {
+                token_type: TokenType::String,
+                literal: Literal::String(path),
+                ..
+            } => Ok((path.clone(), &src[1..])),
+            _ => Err(ParseError {
+                tok: &src[0],
+                err: "Expect module path.".into(),
+            }),
+        }
+    }
+}
+
 impl VarDecl {
     pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let rem = match_tok(src, TokenType::Var, "var")?;
-        let (name, rem) = get_identifier(rem)?;
-        if let Ok(rem) = match_tok(rem, TokenType::Equal, "'=' after var name") {
+        let (binding, rem) = Binding::parse(rem)?;
+        let names = match binding {
+            Binding::Name(name) => return Self::parse_value(name, rem),
+            Binding::Tuple(names) => names,
+        };
+        let rem = match_tok(rem, TokenType::Equal, "'=' after variable names")?;
+        let (values, rem) = Tuple::parse_parenthesized(rem).or_else(|_| Tuple::parse(rem))?;
+        let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+        Ok((VarDecl::Tuple(names, values), rem))
+    }
+
+    fn parse_value<'a>(
+        name: String,
+        src: &'a [Token],
+    ) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        if let Ok(rem) = match_tok(src, TokenType::Equal, "'=' after var name") {
             let (expr, rem) = Expression::parse(rem)?;
             let rem = match_tok(rem, TokenType::Semicolon, ";")?;
-            Ok((
-                VarDecl {
-                    name,
-                    expr: Some(expr),
-                },
-                rem,
-            ))
+            Ok((VarDecl::Name(name, Some(expr)), rem))
         } else {
-            let rem = match_tok(rem, TokenType::Semicolon, ";")?;
-            Ok((VarDecl { name, expr: None }, rem))
+            let rem = match_tok(src, TokenType::Semicolon, ";")?;
+            Ok((VarDecl::Name(name, None), rem))
+        }
+    }
+}
+
+impl Binding {
+    pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let Ok(rem) = match_tok(src, TokenType::LeftParen, "'('") else {
+            let (name, rem) = get_identifier(src)?;
+            return Ok((Binding::Name(name), rem));
+        };
+        let (name, mut rem) = get_identifier(rem)?;
+        let mut names = vec![name];
+        while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+            let (name, r) = get_identifier(r)?;
+            names.push(name);
+            rem = r;
         }
+        let rem = match_tok(rem, TokenType::RightParen, "')' after variable names")?;
+        Ok((Binding::Tuple(names), rem))
     }
 }
 
 impl Function {
-    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+    fn parse<'a>(src: &'a [Token], kind: FunKind) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (name, rem) = get_identifier(src)?;
-        let mut rem = match_tok(rem, TokenType::LeftParen, "(")?;
+        let (params, rem) = Self::parse_params(rem)?;
+        let (body, rem) = function_body(kind, || Block::parse(rem))?;
+        Ok((
+            Function {
+                name,
+                params,
+                body,
+                kind,
+                decorators: vec![],
+            },
+            rem,
+        ))
+    }
+
+    fn parse_getter<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (name, rem) = get_identifier(src)?;
+        let (body, rem) = function_body(FunKind::Plain, || Block::parse(rem))?;
+        let params = None;
+        Ok((
+            Function {
+                name,
+                params,
+                body,
+                kind: FunKind::Plain,
+                decorators: vec![],
+            },
+            rem,
+        ))
+    }
+
+    pub fn parse_lambda<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (kind, rem) = Self::parse_kind(src)?;
+        let (params, rem) = Self::parse_params(rem)?;
+        let (body, rem) = function_body(kind, || match match_tok(rem, TokenType::Arrow, "'=>'") {
+            Ok(rem) => {
+                let (expr, rem) = Expression::parse_single(rem)?;
+                let rtrn = Statement::RtrnStmt(RtrnStmt(Some(Tuple(vec![expr]))));
+                Ok((Block(vec![Declaration::Statement(rtrn)]), rem))
+            }
+            Err(_) => Block::parse(rem),
+        })?;
+        let name = "anonymous".into();
+        Ok((
+            Function {
+                name,
+                params,
+                body,
+                kind,
+                decorators: vec![],
+            },
+            rem,
+        ))
+    }
+
+    fn parse_decorators<'a>(
+        mut src: &'a [Token],
+    ) -> Result<(Vec<Expression>, &'a [Token]), ParseError<'a>> {
+        let mut decorators = vec![];
+        while let Ok(rem) = match_tok(src, TokenType::At, "'@'") {
+            let (decorator, rem) = Expression::parse_single(rem)?;
+            decorators.push(decorator);
+            src = rem;
+        }
+        Ok((decorators, src))
+    }
+
+    fn parse_kind<'a>(src: &'a [Token]) -> Result<(FunKind, &'a [Token]), ParseError<'a>> {
+        if let Ok(rem) = match_word(src, "async", "'async'") {
+            let rem = match_tok(rem, TokenType::Fun, "'fun' after 'async'")?;
+            if rem[0].token_type == TokenType::Star {
+                return Err(ParseError {
+                    tok: &rem[0],
+                    err: "Async generators are not supported.".into(),
+                });
+            }
+            return Ok((FunKind::Async, rem));
+        }
+        let rem = match_tok(src, TokenType::Fun, "fun")?;
+        match match_tok(rem, TokenType::Star, "'*'") {
+            Ok(rem) => Ok((FunKind::Generator, rem)),
+            Err(_) => Ok((FunKind::Plain, rem)),
+        }
+    }
+
+    fn parse_params<'a>(
+        src: &'a [Token],
+    ) -> Result<(Option<Parameters>, &'a [Token]), ParseError<'a>> {
+        let mut rem = match_tok(src, TokenType::LeftParen, "(")?;
         let mut params = None;
-        if let Ok((parm, r)) = Parameters::parse(rem) {
+        if rem[0].token_type != TokenType::RightParen {
+            let (parm, r) = Parameters::parse(rem)?;
             let _ = params.insert(parm);
             rem = r;
         }
         let rem = match_tok(rem, TokenType::RightParen, ")")?;
-        let (body, rem) = Block::parse(rem)?;
-        Ok((Function { name, params, body }, rem))
+        Ok((params, rem))
     }
 }
 
 impl Parameters {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        let (name, mut rem) = get_identifier(src)?;
-        let mut params = Parameters {
-            param: name,
-            rest: None,
-        };
+        let (mut params, mut rem) = Self::parse_one(src, None)?;
 
         while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
-            let (name, r) = get_identifier(r)?;
-            let next = Parameters {
-                param: name,
-                rest: Some(Box::new(params)),
-            };
+            if params.variadic {
+                return Err(ParseError {
+                    tok: &rem[0],
+                    err: "Variadic parameter must be the last one.".into(),
+                });
+            }
+            let (next, r) = Self::parse_one(r, Some(Box::new(params)))?;
+            if next.default.is_none() && !next.variadic {
+                if let Some(prev) = next.rest.as_ref().filter(|p| p.default.is_some()) {
+                    return Err(ParseError {
+                        tok: &rem[1],
+                        err: format!(
+                            "Parameter '{}' needs a default value, as '{}' has one.",
+                            next.param, prev.param
+                        ),
+                    });
+                }
+            }
             params = next;
             rem = r;
         }
 
         Ok((params, rem))
     }
+
+    fn parse_one<'a>(
+        src: &'a [Token],
+        rest: Option<Box<Parameters>>,
+    ) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        if let Ok(rem) = match_tok(src, TokenType::Ellipsis, "'...'") {
+            let (param, rem) = get_identifier(rem)?;
+            let params = Parameters {
+                param,
+                default: None,
+                variadic: true,
+                rest,
+            };
+            return Ok((params, rem));
+        }
+        let (param, mut rem) = get_identifier(src)?;
+        let mut default = None;
+        if let Ok(r) = match_tok(rem, TokenType::Equal, "'=' after parameter name") {
+            let (expr, r) = Expression::parse_single(r)?;
+            let _ = default.insert(expr);
+            rem = r;
+        }
+        let params = Parameters {
+            param,
+            default,
+            variadic: false,
+            rest,
+        };
+        Ok((params, rem))
+    }
+}
+
+/// `static() {}` is a method called `static`.
+fn is_static(src: &[Token]) -> bool {
+    src[0].token_type == TokenType::Class
+        || match_word(src, "static", "'static'")
+            .is_ok_and(|rem| rem[0].token_type == TokenType::Identifier)
+}
+
+/// `async() {}` is a method called `async`.
+fn is_async_method(src: &[Token]) -> bool {
+    match_word(src, "async", "'async'").is_ok_and(|rem| rem[0].token_type == TokenType::Identifier)
 }
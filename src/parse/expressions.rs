@@ -2,26 +2,116 @@ use super::*;
 
 impl Expression {
     pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        let (eq, rem) = Assignment::parse(src)?;
-        Ok((Expression(eq), rem))
+        let (expr, rem) = Assignment::parse(src)?;
+        let line = src[0].line;
+        Ok((Expression { expr, line }, rem))
+    }
+
+    /// Stops at a top-level comma, for argument lists and the like.
+    pub fn parse_single<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (expr, rem) = Assignment::parse_single(src)?;
+        let line = src[0].line;
+        Ok((Expression { expr, line }, rem))
+    }
+}
+
+impl Tuple {
+    pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (first, mut rem) = Expression::parse_single(src)?;
+        let mut items = vec![first];
+        while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+            let (next, r) = Expression::parse_single(r)?;
+            items.push(next);
+            rem = r;
+        }
+        Ok((Tuple(items), rem))
+    }
+
+    /// Only a list of several that ends the statement or a group.
+    pub fn parse_parenthesized<'a>(
+        src: &'a [Token],
+    ) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::LeftParen, "'('")?;
+        let (tuple, rem) = Self::parse(rem)?;
+        let rem = match_tok(rem, TokenType::RightParen, "')' after values")?;
+        match rem[0].token_type {
+            TokenType::Semicolon | TokenType::RightParen if tuple.0.len() > 1 => Ok((tuple, rem)),
+            _ => Err(ParseError {
+                tok: &rem[0],
+                err: "Expect ';' after values.".into(),
+            }),
+        }
     }
 }
 
 impl Assignment {
     pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        Self::parse_ass(src).or_else(|_| Self::parse_logic_or(src))
+        let (mut ass, mut rem) = Self::parse_single(src)?;
+        while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+            let (next, r) = Self::parse_single(r)?;
+            ass = Assignment::Comma(Box::new(ass), Box::new(next));
+            rem = r;
+        }
+        Ok((ass, rem))
+    }
+
+    pub fn parse_single<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        Self::parse_destructure(src)
+            .or_else(|_| Self::parse_ass(src))
+            .or_else(|_| Self::parse_ternary(src))
+    }
+
+    pub fn parse_destructure<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::LeftParen, "'('")?;
+        let (call, mut rem) = Call::parse(rem)?;
+        let mut targets = vec![call];
+        // a single target in parentheses is a plain assignment
+        while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+            let (call, r) = Call::parse(r)?;
+            targets.push(call);
+            rem = r;
+        }
+        if targets.len() < 2 {
+            return Err(ParseError {
+                tok: &rem[0],
+                err: "Expect ','.".into(),
+            });
+        }
+        let rem = match_tok(rem, TokenType::RightParen, "')' after targets")?;
+        let rem = match_tok(rem, TokenType::Equal, "'=' after targets")?;
+        let (value, rem) = Tuple::parse_parenthesized(rem).or_else(|_| {
+            let (value, rem) = Expression::parse_single(rem)?;
+            Ok((Tuple(vec![value]), rem))
+        })?;
+        Ok((Assignment::Destructure(targets, value), rem))
     }
 
     pub fn parse_ass<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (call, rem) = Call::parse(src)?;
-        let rem = match_tok(rem, TokenType::Equal, "'=' in assignment")?;
-        let (rest, rem) = Assignment::parse(rem)?;
-        Ok((Assignment::Assign(call, Box::new(rest)), rem))
+        let (op, rem) = AssignOp::parse(rem)?;
+        let (rest, rem) = Assignment::parse_single(rem)?;
+        Ok((Assignment::Assign(call, op, Box::new(rest)), rem))
     }
 
-    pub fn parse_logic_or<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        let (or, rest) = LogicOr::parse(src)?;
-        Ok((Assignment::LogicOr(or), rest))
+    pub fn parse_ternary<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (pred, rem) = Self::parse_coalesce(src)?;
+        let Ok(rem) = match_tok(rem, TokenType::Question, "'?'") else {
+            return Ok((pred, rem));
+        };
+        let (then, rem) = Assignment::parse_single(rem)?;
+        let rem = match_tok(rem, TokenType::Colon, "':' after then branch of '?'")?;
+        let (els, rem) = Assignment::parse_single(rem)?;
+        let ternary = Assignment::Ternary(Box::new(pred), Box::new(then), Box::new(els));
+        Ok((ternary, rem))
+    }
+
+    pub fn parse_coalesce<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (or, rem) = LogicOr::parse(src)?;
+        let Ok(rem) = match_tok(rem, TokenType::QuestionQuestion, "'??'") else {
+            return Ok((Assignment::LogicOr(or), rem));
+        };
+        let (fallback, rem) = Self::parse_coalesce(rem)?;
+        Ok((Assignment::Coalesce(or, Box::new(fallback)), rem))
     }
 }
 
@@ -150,9 +240,11 @@ impl FactorOp {
         match src[0].token_type {
             TokenType::Star => Ok((FactorOp::Mul, &src[1..])),
             TokenType::Slash => Ok((FactorOp::Div, &src[1..])),
+            TokenType::Percent => Ok((FactorOp::Mod, &src[1..])),
+            TokenType::SlashSlash => Ok((FactorOp::IntDiv, &src[1..])),
             _ => Err(ParseError {
                 tok: &src[0],
-                err: "Expect * or /.".into(),
+                err: "Expect * or / or % or //.".into(),
             }),
         }
     }
@@ -173,6 +265,38 @@ impl Factor {
     }
 }
 
+impl AssignOp {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let op = match src[0].token_type {
+            TokenType::Equal => AssignOp::Equal,
+            TokenType::PlusEqual => AssignOp::Term(TermOp::Plus),
+            TokenType::MinusEqual => AssignOp::Term(TermOp::Minus),
+            TokenType::StarEqual => AssignOp::Factor(FactorOp::Mul),
+            TokenType::SlashEqual => AssignOp::Factor(FactorOp::Div),
+            _ => {
+                return Err(ParseError {
+                    tok: &src[0],
+                    err: "Expect '=' in assignment.".into(),
+                })
+            }
+        };
+        Ok((op, &src[1..]))
+    }
+}
+
+impl IncrOp {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        match src[0].token_type {
+            TokenType::PlusPlus => Ok((IncrOp::Incr, &src[1..])),
+            TokenType::MinusMinus => Ok((IncrOp::Decr, &src[1..])),
+            _ => Err(ParseError {
+                tok: &src[0],
+                err: "Expect ++ or --.".into(),
+            }),
+        }
+    }
+}
+
 impl UnaryOp {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         match src[0].token_type {
@@ -188,17 +312,84 @@ impl UnaryOp {
 
 impl Unary {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        if let Ok(rem) = match_word(src, "await", "'await'") {
+            // outside of an async function it is a name, unless an operand
+            // follows, which a name can't be followed by; the resolver
+            // rejects that await
+            let operand = matches!(
+                rem[0].token_type,
+                TokenType::Identifier
+                    | TokenType::Number
+                    | TokenType::String
+                    | TokenType::Interpolation
+                    | TokenType::True
+                    | TokenType::False
+                    | TokenType::Nil
+                    | TokenType::This
+                    | TokenType::Super
+                    | TokenType::Fun
+            );
+            if IN_ASYNC.get() || operand {
+                let (value, rem) = Unary::parse(rem)?;
+                return Ok((Unary::Await(src[0].clone(), Box::new(value)), rem));
+            }
+        }
         if let Ok((op, rem)) = UnaryOp::parse(src) {
             let (un, rem) = Unary::parse(rem)?;
             Ok((Unary::Un(op, Box::new(un)), rem))
+        } else {
+            let (base, rem) = Self::parse_incr(src)?;
+            match match_tok(rem, TokenType::StarStar, "'**'") {
+                Ok(rem) => {
+                    let (exp, rem) = Unary::parse(rem)?;
+                    Ok((Unary::Pow(Box::new(base), Box::new(exp)), rem))
+                }
+                Err(_) => Ok((base, rem)),
+            }
+        }
+    }
+
+    fn parse_incr<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        if let Ok((op, rem)) = IncrOp::parse(src) {
+            // `--(3)` and `---x` are two negations
+            if let (IncrOp::Decr, TokenType::LeftParen | TokenType::Minus | TokenType::MinusMinus) =
+                (&op, rem[0].token_type)
+            {
+                let (un, rem) = Unary::parse(rem)?;
+                let neg = Unary::Un(UnaryOp::Minus, Box::new(un));
+                return Ok((Unary::Un(UnaryOp::Minus, Box::new(neg)), rem));
+            }
+            let (call, rem) = Call::parse(rem)?;
+            call.assignable(&src[0])?;
+            Ok((Unary::PreIncr(op, call), rem))
         } else {
             let (pr, rem) = Call::parse(src)?;
-            Ok((Unary::Call(pr), rem))
+            match IncrOp::parse(rem) {
+                Ok((op, r)) => {
+                    pr.assignable(&rem[0])?;
+                    Ok((Unary::PostIncr(pr, op), r))
+                }
+                Err(_) => Ok((Unary::Call(pr), rem)),
+            }
         }
     }
 }
 
 impl Call {
+    fn assignable<'a>(&self, op: &'a Token) -> Result<(), ParseError<'a>> {
+        let place = match self.rest.last() {
+            None => matches!(self.prime, Primary::Identifier(_)),
+            Some(last) => matches!(last, Calling::Index(_) | Calling::Mthd(_)),
+        };
+        if place {
+            return Ok(());
+        }
+        Err(ParseError {
+            tok: op,
+            err: "Invalid assignment target.".into(),
+        })
+    }
+
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (prime, mut rem) = Primary::parse(src)?;
         let mut callings = vec![];
@@ -229,13 +420,22 @@ impl Calling {
                     Ok((Calling::FuncCall(None), rem))
                 }
             }
+            TokenType::LeftBracket => {
+                let (expr, rem) = Expression::parse(&src[1..])?;
+                let rem = match_tok(rem, TokenType::RightBracket, "']' after index")?;
+                Ok((Calling::Index(Box::new(expr)), rem))
+            }
             TokenType::Dot => {
-                let (id, rem) = get_identifier(&src[1..])?;
+                let (id, rem) = get_property(&src[1..])?;
                 Ok((Calling::Mthd(id), rem))
             }
+            TokenType::QuestionDot => {
+                let (id, rem) = get_property(&src[1..])?;
+                Ok((Calling::OptMthd(id), rem))
+            }
             _ => Err(ParseError {
                 tok: &src[0],
-                err: "Expect '(' or '.'".into(),
+                err: "Expect '(', '[', '.' or '?.'".into(),
             }),
         }
     }
@@ -258,6 +458,42 @@ impl Primary {
                 })?;
                 Ok((Primary::String(s), &src[1..]))
             }
+            TokenType::Interpolation => {
+                let mut parts = vec![];
+                let mut rem = src;
+                // pieces alternate with expressions, and a plain string ends it
+                loop {
+                    let last = match rem[0].token_type {
+                        TokenType::Interpolation => false,
+                        TokenType::String => true,
+                        _ => {
+                            return Err(ParseError {
+                                tok: &rem[0],
+                                err: "Expect '}' after interpolated expression.".into(),
+                            })
+                        }
+                    };
+                    let s = rem[0].literal.get_string().unwrap_or_default();
+                    parts.push(StrPart::Str(s));
+                    if last {
+                        break;
+                    }
+                    let (expr, r) = Expression::parse(&rem[1..])?;
+                    parts.push(StrPart::Expr(expr));
+                    rem = r;
+                }
+                Ok((Primary::Interpolation(parts), &rem[1..]))
+            }
+            TokenType::Fun => {
+                let (func, rem) = Function::parse_lambda(src)?;
+                Ok((Primary::Lambda(Box::new(func)), rem))
+            }
+            TokenType::Identifier
+                if src[0].lexeme == "async" && src[1].token_type == TokenType::Fun =>
+            {
+                let (func, rem) = Function::parse_lambda(src)?;
+                Ok((Primary::Lambda(Box::new(func)), rem))
+            }
             TokenType::True => Ok((Primary::Boolean(true), &src[1..])),
             TokenType::False => Ok((Primary::Boolean(false), &src[1..])),
             TokenType::Nil => Ok((Primary::Nil, &src[1..])),
@@ -271,6 +507,30 @@ impl Primary {
                 let (id, rem) = get_identifier(src)?;
                 Ok((Primary::Identifier(id), rem))
             }
+            TokenType::LeftBracket => {
+                if let Ok((elems, rem)) = Arguments::parse(&src[1..]) {
+                    let rem = match_tok(rem, TokenType::RightBracket, "']' after list elements")?;
+                    Ok((Primary::List(Some(Box::new(elems))), rem))
+                } else {
+                    let rem = match_tok(&src[1..], TokenType::RightBracket, "']'")?;
+                    Ok((Primary::List(None), rem))
+                }
+            }
+            TokenType::LeftBrace => {
+                let mut entries = vec![];
+                let mut rem = &src[1..];
+                if let Ok((entry, r)) = Self::parse_entry(rem) {
+                    entries.push(entry);
+                    rem = r;
+                    while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+                        let (entry, r) = Self::parse_entry(r)?;
+                        entries.push(entry);
+                        rem = r;
+                    }
+                }
+                let rem = match_tok(rem, TokenType::RightBrace, "'}' after map entries")?;
+                Ok((Primary::Map(entries), rem))
+            }
             TokenType::LeftParen => {
                 let (expr, rst) = Expression::parse(&src[1..])?;
                 let rst = match_tok(rst, TokenType::RightParen, "expression")?;
@@ -285,19 +545,37 @@ impl Primary {
     }
 }
 
+impl Primary {
+    fn parse_entry<'a>(
+        src: &'a [Token],
+    ) -> Result<((Expression, Expression), &'a [Token]), ParseError<'a>> {
+        let (key, rem) = Expression::parse_single(src)?;
+        let rem = match_tok(rem, TokenType::Colon, "':' after map key")?;
+        let (value, rem) = Expression::parse_single(rem)?;
+        Ok(((key, value), rem))
+    }
+}
+
 impl Arguments {
     pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        let (expr, mut rem) = Expression::parse(src)?;
-        let mut arguments = Arguments { expr, rest: None };
+        let (mut arguments, mut rem) = Self::parse_one(src, None)?;
         while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
-            let (expr, r) = Expression::parse(r)?;
-            let next = Arguments {
-                expr,
-                rest: Some(Box::new(arguments)),
-            };
+            let (next, r) = Self::parse_one(r, Some(Box::new(arguments)))?;
             arguments = next;
             rem = r;
         }
         Ok((arguments, rem))
     }
+
+    fn parse_one<'a>(
+        src: &'a [Token],
+        rest: Option<Box<Arguments>>,
+    ) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (spread, rem) = match match_tok(src, TokenType::Ellipsis, "'...'") {
+            Ok(rem) => (true, rem),
+            Err(_) => (false, src),
+        };
+        let (expr, rem) = Expression::parse_single(rem)?;
+        Ok((Arguments { expr, spread, rest }, rem))
+    }
 }
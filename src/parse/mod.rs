@@ -2,8 +2,23 @@ pub mod declarations;
 pub mod expressions;
 pub mod statements;
 
+use std::cell::Cell;
+
 use crate::ast::*;
-use crate::token::{Token, TokenType};
+use crate::scan::Scanner;
+use crate::token::{Literal, Token, TokenType};
+
+thread_local! {
+    /// Whether `await` is a keyword rather than a name.
+    static IN_ASYNC: Cell<bool> = const { Cell::new(false) };
+}
+
+fn function_body<T>(kind: FunKind, parse: impl FnOnce() -> T) -> T {
+    let outer = IN_ASYNC.replace(kind == FunKind::Async);
+    let res = parse();
+    IN_ASYNC.set(outer);
+    res
+}
 
 pub struct Parser<'a> {
     src: &'a [Token],
@@ -24,12 +39,30 @@ impl<'a> Parser<'a> {
         }
     }
     pub fn parse(&mut self) -> Result<(), ParseError<'a>> {
-        let program = Program::parse(&self.src)?;
+        let program = Program::parse(self.src)?;
         let _ = self.program.insert(program);
         Ok(())
     }
 }
 
+/// Rereads a `//` as a comment where integer division fails to parse.
+pub fn settle_comments(scanner: &mut Scanner) {
+    while scanner.has_divisions() {
+        let at = match Parser::new(&scanner.tokens).parse() {
+            Ok(()) => return,
+            Err(_) if is_expression(&scanner.tokens) => return,
+            Err(e) => scanner.tokens.iter().position(|t| std::ptr::eq(t, e.tok)),
+        };
+        if !at.is_some_and(|at| scanner.reread_as_comment(at)) {
+            return;
+        }
+    }
+}
+
+fn is_expression(src: &[Token]) -> bool {
+    Expression::parse(src).is_ok_and(|(_, rem)| rem[0].token_type == TokenType::Eof)
+}
+
 impl Program {
     fn parse<'a>(mut src: &'a [Token]) -> Result<Self, ParseError<'a>> {
         let mut program = Program {
@@ -59,6 +92,66 @@ fn get_identifier<'a>(src: &'a [Token]) -> Result<(String, &'a [Token]), ParseEr
     }
 }
 
+/// Keywords can be property names, as in `promise.catch(handler)`.
+fn get_property<'a>(src: &'a [Token]) -> Result<(String, &'a [Token]), ParseError<'a>> {
+    match &src[0] {
+        Token { lexeme, .. } if lexeme.starts_with(|c: char| c.is_alphabetic() || c == '_') => {
+            Ok((lexeme.clone(), &src[1..]))
+        }
+        _ => get_identifier(src),
+    }
+}
+
+type Alternative<T> = for<'a> fn(&'a [Token]) -> Result<(T, &'a [Token]), ParseError<'a>>;
+
+/// The error comes from the alternative that got furthest.
+fn one_of<'a, T>(
+    src: &'a [Token],
+    alternatives: &[Alternative<T>],
+) -> Result<(T, &'a [Token]), ParseError<'a>> {
+    let mut furthest: Option<ParseError<'a>> = None;
+    for alt in alternatives {
+        match alt(src) {
+            Ok(res) => return Ok(res),
+            Err(e) => {
+                // all the errors point into `src`, so their addresses give their order
+                let at = |err: &ParseError| std::ptr::from_ref(err.tok);
+                if furthest.as_ref().map_or(true, |f| at(f) <= at(&e)) {
+                    furthest = Some(e);
+                }
+            }
+        }
+    }
+    Err(furthest.expect("at least one alternative"))
+}
+
+/// For keywords that can still name variables everywhere else.
+fn match_word<'a>(
+    src: &'a [Token],
+    word: &str,
+    expect: &str,
+) -> Result<&'a [Token], ParseError<'a>> {
+    if src[0].token_type == TokenType::Identifier && src[0].lexeme == word {
+        Ok(&src[1..])
+    } else {
+        Err(ParseError {
+            tok: &src[0],
+            err: format!("Expect {}.", expect),
+        })
+    }
+}
+
+/// A `{` starting a statement is a block, not a map literal.
+fn no_map_literal(src: &[Token]) -> Result<(), ParseError<'_>> {
+    if src[0].token_type == TokenType::LeftBrace {
+        return Err(ParseError {
+            tok: &src[0],
+            err: "Expect expression.".into(),
+        });
+    }
+    Ok(())
+}
+
 fn match_tok<'a>(
     src: &'a [Token],
     tp: TokenType,
@@ -2,13 +2,25 @@ use super::*;
 
 impl Statement {
     pub fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
-        Self::parse_expr_stmt(src)
-            .or_else(|_| Self::parse_for_stmt(src))
-            .or_else(|_| Self::parse_if_stmt(src))
-            .or_else(|_| Self::parse_prnt_stmt(src))
-            .or_else(|_| Self::parse_rtrn_stmt(src))
-            .or_else(|_| Self::parse_while_stmt(src))
-            .or_else(|_| Self::parse_block(src))
+        one_of(
+            src,
+            &[
+                Self::parse_expr_stmt,
+                Self::parse_brk_stmt,
+                Self::parse_cnt_stmt,
+                Self::parse_for_in_stmt,
+                Self::parse_for_stmt,
+                Self::parse_if_stmt,
+                Self::parse_match_stmt,
+                Self::parse_prnt_stmt,
+                Self::parse_rtrn_stmt,
+                Self::parse_thrw_stmt,
+                Self::parse_try_stmt,
+                Self::parse_while_stmt,
+                Self::parse_yield_stmt,
+                Self::parse_block,
+            ],
+        )
     }
 
     fn parse_expr_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
@@ -16,11 +28,31 @@ impl Statement {
         Ok((Statement::ExprStmt(stmt), rem))
     }
 
+    fn parse_brk_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = BrkStmt::parse(src)?;
+        Ok((Statement::BrkStmt(stmt), rem))
+    }
+
+    fn parse_cnt_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = CntStmt::parse(src)?;
+        Ok((Statement::CntStmt(stmt), rem))
+    }
+
     fn parse_if_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (stmt, rem) = IfStmt::parse(src)?;
         Ok((Statement::IfStmt(stmt), rem))
     }
 
+    fn parse_match_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = MatchStmt::parse(src)?;
+        Ok((Statement::MatchStmt(stmt), rem))
+    }
+
+    fn parse_for_in_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = ForInStmt::parse(src)?;
+        Ok((Statement::ForInStmt(stmt), rem))
+    }
+
     fn parse_for_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (stmt, rem) = ForStmt::parse(src)?;
         Ok((Statement::ForStmt(stmt), rem))
@@ -36,11 +68,26 @@ impl Statement {
         Ok((Statement::RtrnStmt(stmt), rem))
     }
 
+    fn parse_thrw_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = ThrwStmt::parse(src)?;
+        Ok((Statement::ThrwStmt(stmt), rem))
+    }
+
+    fn parse_try_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = TryStmt::parse(src)?;
+        Ok((Statement::TryStmt(stmt), rem))
+    }
+
     fn parse_prnt_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (stmt, rem) = PrntStmt::parse(src)?;
         Ok((Statement::PrntStmt(stmt), rem))
     }
 
+    fn parse_yield_stmt<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let (stmt, rem) = YieldStmt::parse(src)?;
+        Ok((Statement::YieldStmt(stmt), rem))
+    }
+
     fn parse_block<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let (stmt, rem) = Block::parse(src)?;
         Ok((Statement::Block(stmt), rem))
@@ -49,12 +96,29 @@ impl Statement {
 
 impl ExprStmt {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        no_map_literal(src)?;
         let (expr, rem) = Expression::parse(src)?;
         let rem = match_tok(rem, TokenType::Semicolon, "';' after expression")?;
         Ok((ExprStmt(expr), rem))
     }
 }
 
+impl BrkStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::Break, "'break'")?;
+        let rem = match_tok(rem, TokenType::Semicolon, "';' after 'break'")?;
+        Ok((BrkStmt(src[0].clone()), rem))
+    }
+}
+
+impl CntStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::Continue, "'continue'")?;
+        let rem = match_tok(rem, TokenType::Semicolon, "';' after 'continue'")?;
+        Ok((CntStmt(src[0].clone()), rem))
+    }
+}
+
 impl IfStmt {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let rem = match_tok(src, TokenType::If, "if")?;
@@ -79,17 +143,130 @@ impl IfStmt {
     }
 }
 
+impl MatchStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_word(src, "match", "'match'")?;
+        let rem = match_tok(rem, TokenType::LeftParen, "'(' after 'match'")?;
+        let (subject, rem) = Expression::parse(rem)?;
+        let rem = match_tok(rem, TokenType::RightParen, "')' after match subject")?;
+        let mut rem = match_tok(rem, TokenType::LeftBrace, "'{' before match arms")?;
+        let mut arms = vec![];
+        while rem[0].token_type != TokenType::RightBrace {
+            let (arm, r) = MatchArm::parse(rem)?;
+            arms.push(arm);
+            rem = r;
+        }
+        Ok((MatchStmt { subject, arms }, &rem[1..]))
+    }
+}
+
+impl MatchArm {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let word = match src[0].token_type {
+            TokenType::Identifier => src[0].lexeme.as_str(),
+            _ => "",
+        };
+        let (patterns, rem) = match word {
+            "default" => (None, &src[1..]),
+            "case" => {
+                let (pattern, mut rem) = Pattern::parse(&src[1..])?;
+                let mut patterns = vec![pattern];
+                while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+                    let (pattern, r) = Pattern::parse(r)?;
+                    patterns.push(pattern);
+                    rem = r;
+                }
+                (Some(patterns), rem)
+            }
+            _ => {
+                return Err(ParseError {
+                    tok: &src[0],
+                    err: "Expect 'case' or 'default'.".into(),
+                })
+            }
+        };
+        let rem = match_tok(rem, TokenType::Arrow, "'=>' after pattern")?;
+        let (body, rem) = Statement::parse(rem)?;
+        let arm = MatchArm {
+            tok: src[0].clone(),
+            patterns,
+            body: Box::new(body),
+        };
+        Ok((arm, rem))
+    }
+}
+
+impl Pattern {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        if let Ok((name, rem)) = get_identifier(src) {
+            let Ok(mut rem) = match_tok(rem, TokenType::LeftParen, "'(' after class name") else {
+                return Ok((Pattern::Class(name, vec![]), rem));
+            };
+            let mut fields = vec![];
+            if rem[0].token_type != TokenType::RightParen {
+                let (field, r) = get_identifier(rem)?;
+                fields.push(field);
+                rem = r;
+                while let Ok(r) = match_tok(rem, TokenType::Comma, ",") {
+                    let (field, r) = get_identifier(r)?;
+                    fields.push(field);
+                    rem = r;
+                }
+            }
+            let rem = match_tok(rem, TokenType::RightParen, "')' after field names")?;
+            return Ok((Pattern::Class(name, fields), rem));
+        }
+        let (value, rem) = Self::parse_literal(src)?;
+        let Ok(r) = match_tok(rem, TokenType::DotDot, "'..'") else {
+            return Ok((Pattern::Value(value), rem));
+        };
+        let (end, r) = Self::parse_literal(r)?;
+        match (value, end) {
+            (Primary::Number(lo), Primary::Number(hi)) => Ok((Pattern::Range(lo, hi), r)),
+            _ => Err(ParseError {
+                tok: &src[0],
+                err: "Range bounds must be numbers.".into(),
+            }),
+        }
+    }
+
+    fn parse_literal<'a>(src: &'a [Token]) -> Result<(Primary, &'a [Token]), ParseError<'a>> {
+        let (negate, rem) = match match_tok(src, TokenType::Minus, "'-'") {
+            Ok(rem) => (true, rem),
+            Err(_) => (false, src),
+        };
+        let value = match (&rem[0].token_type, &rem[0].literal) {
+            (TokenType::Number, Literal::Number(n)) if negate => Primary::Number(-n),
+            (TokenType::Number, Literal::Number(n)) => Primary::Number(*n),
+            (TokenType::String, Literal::String(s)) if !negate => Primary::String(s.clone()),
+            (TokenType::True, _) if !negate => Primary::Boolean(true),
+            (TokenType::False, _) if !negate => Primary::Boolean(false),
+            (TokenType::Nil, _) if !negate => Primary::Nil,
+            _ => {
+                return Err(ParseError {
+                    tok: &rem[0],
+                    err: "Expect pattern.".into(),
+                })
+            }
+        };
+        Ok((value, &rem[1..]))
+    }
+}
+
 impl ForStmt {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let rem = match_tok(src, TokenType::For, "for")?;
         let rem = match_tok(rem, TokenType::LeftParen, "'(' after for")?;
+        no_map_literal(rem)?;
         let (first_dec, mut rem) = ForDec::parse(rem)?;
+        no_map_literal(rem)?;
         let mut scnd_expr = None;
         if let Ok((scnd, r)) = Expression::parse(rem) {
             let _ = scnd_expr.insert(scnd);
             rem = r;
         }
         let mut rem = match_tok(rem, TokenType::Semicolon, "';' in for")?;
+        no_map_literal(rem)?;
         let mut thrd_expr = None;
         if let Ok((thrd, r)) = Expression::parse(rem) {
             let _ = thrd_expr.insert(thrd);
@@ -109,6 +286,34 @@ impl ForStmt {
     }
 }
 
+impl ForInStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::For, "for")?;
+        let rem = match_tok(rem, TokenType::LeftParen, "'(' after for")?;
+        let rem = match_tok(rem, TokenType::Var, "var")?;
+        let (binding, rem) = Binding::parse(rem)?;
+        let rem = match_word(rem, "in", "'in' after loop variable")?;
+        let (iterable, mut rem) = Expression::parse(rem)?;
+        let mut end = None;
+        if let Ok(r) = match_tok(rem, TokenType::DotDot, "'..'") {
+            let (e, r) = Expression::parse(r)?;
+            let _ = end.insert(e);
+            rem = r;
+        }
+        let rem = match_tok(rem, TokenType::RightParen, "')' after loop iterable")?;
+        let (body, rem) = Statement::parse(rem)?;
+        Ok((
+            ForInStmt {
+                binding,
+                iterable,
+                end,
+                body: Box::new(body),
+            },
+            rem,
+        ))
+    }
+}
+
 impl ForDec {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         if let Ok((var, rem)) = VarDecl::parse(src) {
@@ -139,10 +344,24 @@ impl WhileStmt {
     }
 }
 
+impl YieldStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::Yield, "'yield'")?;
+        let tok = src[0].clone();
+        if let Ok((e, rem)) = Expression::parse(rem) {
+            let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+            Ok((YieldStmt { tok, expr: Some(e) }, rem))
+        } else {
+            let rem = match_tok(rem, TokenType::Semicolon, ";")?;
+            Ok((YieldStmt { tok, expr: None }, rem))
+        }
+    }
+}
+
 impl RtrnStmt {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let rem = match_tok(src, TokenType::Return, "'return'")?;
-        if let Ok((e, rem)) = Expression::parse(rem) {
+        if let Ok((e, rem)) = Tuple::parse(rem) {
             let rem = match_tok(rem, TokenType::Semicolon, ";")?;
             Ok((RtrnStmt(Some(e)), rem))
         } else {
@@ -152,6 +371,51 @@ impl RtrnStmt {
     }
 }
 
+impl ThrwStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::Throw, "'throw'")?;
+        let (expr, rem) = Expression::parse(rem)?;
+        let rem = match_tok(rem, TokenType::Semicolon, "';' after thrown value")?;
+        Ok((ThrwStmt(expr), rem))
+    }
+}
+
+impl TryStmt {
+    fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
+        let rem = match_tok(src, TokenType::Try, "'try'")?;
+        let (body, mut rem) = Block::parse(rem)?;
+        let mut catch = None;
+        if let Ok(r) = match_tok(rem, TokenType::Catch, "'catch'") {
+            let r = match_tok(r, TokenType::LeftParen, "'(' after 'catch'")?;
+            let (name, r) = get_identifier(r)?;
+            let r = match_tok(r, TokenType::RightParen, "')' after catch variable")?;
+            let (block, r) = Block::parse(r)?;
+            let _ = catch.insert((name, block));
+            rem = r;
+        }
+        let mut finally = None;
+        if let Ok(r) = match_tok(rem, TokenType::Finally, "'finally'") {
+            let (block, r) = Block::parse(r)?;
+            let _ = finally.insert(block);
+            rem = r;
+        }
+        if catch.is_none() && finally.is_none() {
+            return Err(ParseError {
+                tok: &rem[0],
+                err: "Expect 'catch' or 'finally' after try block.".into(),
+            });
+        }
+        Ok((
+            TryStmt {
+                body,
+                catch,
+                finally,
+            },
+            rem,
+        ))
+    }
+}
+
 impl PrntStmt {
     fn parse<'a>(src: &'a [Token]) -> Result<(Self, &'a [Token]), ParseError<'a>> {
         let rem = match_tok(src, TokenType::Print, "'print'")?;
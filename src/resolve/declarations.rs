@@ -0,0 +1,87 @@
+use super::*;
+
+impl Resolve for Declaration {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            Declaration::ClassDecl(class_decl) => class_decl.resolve(resolver),
+            Declaration::EnumDecl(_) => Ok(()),
+            Declaration::FunDecl(fun_decl) => fun_decl.0.resolve(resolver),
+            Declaration::ImportDecl(import_decl) => import_decl.resolve(resolver),
+            Declaration::RecordDecl(_) => Ok(()),
+            Declaration::VarDecl(var_decl) => var_decl.resolve(resolver),
+            Declaration::Statement(statement) => statement.resolve(resolver),
+        }
+    }
+}
+
+impl Resolve for ClassDecl {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        for field in &self.fields {
+            if let Some(e) = &field.expr {
+                e.resolve(resolver)?;
+            }
+        }
+        let methods = [&self.statics, &self.getters, &self.setters, &self.functions];
+        for f in methods.into_iter().flatten() {
+            f.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for ImportDecl {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        if resolver.scope_depth > 0 {
+            return Err(ResolveError {
+                tok: self.tok.clone(),
+                err: "Can't import outside of top-level code.".into(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for VarDecl {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            VarDecl::Name(_, Some(e)) => e.resolve(resolver),
+            VarDecl::Name(_, None) => Ok(()),
+            VarDecl::Tuple(_, values) => values.resolve(resolver),
+        }
+    }
+}
+
+impl Resolve for Function {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        // decorators run where the function is declared, not inside it
+        for d in &self.decorators {
+            d.resolve(resolver)?;
+        }
+        // a function body starts outside of any loop, even when declared in one
+        let loop_depth = std::mem::take(&mut resolver.loop_depth);
+        // default values are evaluated by the call, before an async body
+        // starts, so they can't await
+        let function = std::mem::replace(&mut resolver.function, FunKind::Plain);
+        let res = match &self.params {
+            Some(params) => params.resolve(resolver),
+            None => Ok(()),
+        };
+        resolver.function = self.kind;
+        let res = res.and_then(|_| self.body.resolve(resolver));
+        resolver.loop_depth = loop_depth;
+        resolver.function = function;
+        res
+    }
+}
+
+impl Resolve for Parameters {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        if let Some(default) = &self.default {
+            default.resolve(resolver)?;
+        }
+        if let Some(rest) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+
+impl Resolve for Expression {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.expr.resolve(resolver)
+    }
+}
+
+impl Resolve for Tuple {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        for value in &self.0 {
+            value.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Assignment {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            Assignment::Assign(call, _, assignment) => {
+                call.resolve(resolver)?;
+                assignment.resolve(resolver)
+            }
+            Assignment::Destructure(targets, value) => {
+                for t in targets {
+                    t.resolve(resolver)?;
+                }
+                value.resolve(resolver)
+            }
+            Assignment::Comma(left, right) => {
+                left.resolve(resolver)?;
+                right.resolve(resolver)
+            }
+            Assignment::Ternary(pred, then, els) => {
+                pred.resolve(resolver)?;
+                then.resolve(resolver)?;
+                els.resolve(resolver)
+            }
+            Assignment::Coalesce(value, fallback) => {
+                value.resolve(resolver)?;
+                fallback.resolve(resolver)
+            }
+            Assignment::LogicOr(logic_or) => logic_or.resolve(resolver),
+        }
+    }
+}
+
+impl Resolve for LogicOr {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.and.resolve(resolver)?;
+        if let Some(rest) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for LogicAnd {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.eq.resolve(resolver)?;
+        if let Some(rest) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Equality {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.comparision.resolve(resolver)?;
+        if let Some((_, rest)) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Comparision {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.term.resolve(resolver)?;
+        if let Some((_, rest)) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Term {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.factor.resolve(resolver)?;
+        if let Some((_, rest)) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Factor {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.unary.resolve(resolver)?;
+        if let Some((_, rest)) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Unary {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            Unary::Un(_, unary) => unary.resolve(resolver),
+            Unary::PreIncr(_, call) | Unary::PostIncr(call, _) => call.resolve(resolver),
+            Unary::Pow(base, exp) => {
+                base.resolve(resolver)?;
+                exp.resolve(resolver)
+            }
+            Unary::Await(tok, value) => {
+                if resolver.function != FunKind::Async {
+                    return Err(ResolveError {
+                        tok: tok.clone(),
+                        err: "Can't await outside of an async function.".into(),
+                    });
+                }
+                value.resolve(resolver)
+            }
+            Unary::Call(call) => call.resolve(resolver),
+        }
+    }
+}
+
+impl Resolve for Call {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.prime.resolve(resolver)?;
+        for c in &self.rest {
+            c.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Calling {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            Calling::FuncCall(Some(arguments)) => arguments.resolve(resolver),
+            Calling::Index(i) => i.resolve(resolver),
+            Calling::FuncCall(None) | Calling::Mthd(_) | Calling::OptMthd(_) => Ok(()),
+        }
+    }
+}
+
+impl Resolve for Primary {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            Primary::Interpolation(parts) => {
+                for p in parts {
+                    if let StrPart::Expr(e) = p {
+                        e.resolve(resolver)?;
+                    }
+                }
+                Ok(())
+            }
+            Primary::List(Some(elems)) => elems.resolve(resolver),
+            Primary::Map(entries) => {
+                for (k, v) in entries {
+                    k.resolve(resolver)?;
+                    v.resolve(resolver)?;
+                }
+                Ok(())
+            }
+            Primary::Lambda(func) => func.resolve(resolver),
+            Primary::ParenExpr(expression) => expression.resolve(resolver),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Resolve for Arguments {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.expr.resolve(resolver)?;
+        if let Some(rest) = &self.rest {
+            rest.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+pub mod declarations;
+pub mod expressions;
+pub mod statements;
+
+use crate::ast::*;
+use crate::token::Token;
+
+#[derive(Default)]
+pub struct Resolver {
+    loop_depth: usize,
+    /// The innermost function, which decides whether `yield` or `await`
+    /// is allowed.
+    function: FunKind,
+    // blocks and function bodies we are inside of; zero at the top level
+    scope_depth: usize,
+    /// Problems that don't stop the program from running.
+    pub warnings: Vec<ResolveError>,
+}
+
+#[derive(Debug)]
+pub struct ResolveError {
+    pub tok: Token,
+    pub err: String,
+}
+
+pub trait Resolve {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError>;
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn in_loop<T: Resolve + ?Sized>(&mut self, body: &T) -> Result<(), ResolveError> {
+        self.loop_depth += 1;
+        let res = body.resolve(self);
+        self.loop_depth -= 1;
+        res
+    }
+}
+
+impl Resolve for Program {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        for d in &self.declarations {
+            d.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
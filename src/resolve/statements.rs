@@ -0,0 +1,116 @@
+use super::*;
+
+impl Resolve for Statement {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match self {
+            Statement::BrkStmt(BrkStmt(tok)) | Statement::CntStmt(CntStmt(tok)) => {
+                if resolver.loop_depth == 0 {
+                    Err(ResolveError {
+                        tok: tok.clone(),
+                        err: format!("Can't use '{}' outside of a loop.", tok.lexeme),
+                    })
+                } else {
+                    Ok(())
+                }
+            }
+            Statement::ExprStmt(ExprStmt(e))
+            | Statement::PrntStmt(PrntStmt(e))
+            | Statement::ThrwStmt(ThrwStmt(e)) => e.resolve(resolver),
+            Statement::RtrnStmt(RtrnStmt(e)) => match e {
+                Some(e) => e.resolve(resolver),
+                None => Ok(()),
+            },
+            Statement::ForStmt(for_stmt) => for_stmt.resolve(resolver),
+            Statement::ForInStmt(for_in_stmt) => {
+                for_in_stmt.iterable.resolve(resolver)?;
+                if let Some(end) = &for_in_stmt.end {
+                    end.resolve(resolver)?;
+                }
+                resolver.in_loop(for_in_stmt.body.as_ref())
+            }
+            Statement::IfStmt(if_stmt) => if_stmt.resolve(resolver),
+            Statement::MatchStmt(match_stmt) => match_stmt.resolve(resolver),
+            Statement::TryStmt(try_stmt) => try_stmt.resolve(resolver),
+            Statement::WhileStmt(while_stmt) => {
+                while_stmt.pred.resolve(resolver)?;
+                resolver.in_loop(while_stmt.stmt.as_ref())
+            }
+            Statement::YieldStmt(yield_stmt) => {
+                if resolver.function != FunKind::Generator {
+                    return Err(ResolveError {
+                        tok: yield_stmt.tok.clone(),
+                        err: "Can't yield outside of a generator.".into(),
+                    });
+                }
+                yield_stmt
+                    .expr
+                    .as_ref()
+                    .map_or(Ok(()), |e| e.resolve(resolver))
+            }
+            Statement::Block(block) => block.resolve(resolver),
+        }
+    }
+}
+
+impl Resolve for ForStmt {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        match &self.first_dec {
+            ForDec::VarDecl(var_decl) => var_decl.resolve(resolver)?,
+            ForDec::ExprStmt(ExprStmt(e)) => e.resolve(resolver)?,
+            ForDec::Nil => {}
+        }
+        for e in [&self.scnd_expr, &self.thrd_expr].into_iter().flatten() {
+            e.resolve(resolver)?;
+        }
+        resolver.in_loop(self.body.as_ref())
+    }
+}
+
+impl Resolve for IfStmt {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.pred.resolve(resolver)?;
+        self.if_stmt.resolve(resolver)?;
+        if let Some(el) = &self.else_stmt {
+            el.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for TryStmt {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.body.resolve(resolver)?;
+        let catch = self.catch.as_ref().map(|(_, block)| block);
+        for block in [catch, self.finally.as_ref()].into_iter().flatten() {
+            block.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for MatchStmt {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        self.subject.resolve(resolver)?;
+        let mut default = false;
+        for arm in &self.arms {
+            if default {
+                resolver.warnings.push(ResolveError {
+                    tok: arm.tok.clone(),
+                    err: "Unreachable case after 'default'.".into(),
+                });
+            }
+            default |= arm.patterns.is_none();
+            arm.body.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolve for Block {
+    fn resolve(&self, resolver: &mut Resolver) -> Result<(), ResolveError> {
+        resolver.scope_depth += 1;
+        let res = self.0.iter().try_for_each(|d| d.resolve(resolver));
+        resolver.scope_depth -= 1;
+        res
+    }
+}
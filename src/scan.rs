@@ -1,9 +1,14 @@
+use std::{iter::Peekable, slice::Iter};
+
 use crate::token::{Literal, Token, TokenType, KEYWORDS};
 
 pub struct Scanner {
     src: Vec<char>,
     pub tokens: Vec<Token>,
     pub errors: Vec<ScanError>,
+    /// The `//`s read as integer division, by token index and offset.
+    divisions: Vec<(usize, usize)>,
+    comments: Vec<usize>,
 }
 
 pub struct ScanError {
@@ -17,29 +22,168 @@ impl Scanner {
             src: input.chars().collect(),
             tokens: vec![],
             errors: vec![],
+            divisions: vec![],
+            comments: vec![],
         }
     }
 
     pub fn scan(&mut self) {
+        self.scan_tokens();
+        // a `//` that the rest of its line can't follow as an operator is a
+        // comment after all
+        while let Some(at) = self.errors.iter().find_map(|e| self.division(e.line)) {
+            self.comments.push(at);
+            self.scan_tokens();
+        }
+    }
+
+    /// Rescans the last `//` on the line of token `at` as a comment.
+    pub fn reread_as_comment(&mut self, at: usize) -> bool {
+        let Some(offset) = self.division(self.tokens[at].line) else {
+            return false;
+        };
+        self.comments.push(offset);
+        self.scan_tokens();
+        true
+    }
+
+    pub fn has_divisions(&self) -> bool {
+        !self.divisions.is_empty()
+    }
+
+    fn division(&self, line: usize) -> Option<usize> {
+        self.divisions
+            .iter()
+            .rev()
+            .find(|&&(i, _)| self.tokens[i].line == line)
+            .map(|&(_, offset)| offset)
+    }
+
+    fn scan_tokens(&mut self) {
+        self.tokens.clear();
+        self.errors.clear();
+        self.divisions.clear();
         let mut iter = self.src.iter().peekable();
         let mut line = 1;
+        // for each `${ ... }` we are currently scanning, the braces open
+        // inside it and the line its string literal started on
+        let mut interp: Vec<(usize, usize)> = vec![];
 
         while let Some(c) = iter.next() {
             let (tp, eme, lrl) = match c {
+                '@' => (TokenType::At, "@".into(), Literal::None),
                 '(' => (TokenType::LeftParen, "(".into(), Literal::None),
                 ')' => (TokenType::RightParen, ")".into(), Literal::None),
-                '{' => (TokenType::LeftBrace, "{".into(), Literal::None),
-                '}' => (TokenType::RightBrace, "}".into(), Literal::None),
+                '{' => {
+                    if let Some((depth, _)) = interp.last_mut() {
+                        *depth += 1;
+                    }
+                    (TokenType::LeftBrace, "{".into(), Literal::None)
+                }
+                // a `}` closing an interpolation resumes the string it was in
+                &c @ ('"' | '}') if c == '"' || interp.last().is_some_and(|i| i.0 == 0) => {
+                    let start = match c {
+                        '}' => interp.pop().map_or(line, |(_, start)| start),
+                        _ => line,
+                    };
+                    match Self::string(&mut iter, &mut line, c) {
+                        Some(tok) => {
+                            if tok.0 == TokenType::Interpolation {
+                                interp.push((0, start));
+                            }
+                            tok
+                        }
+                        None => {
+                            // inside an interpolation it is the outermost
+                            // literal that is left open
+                            let line = match interp.first() {
+                                Some(&(_, outer)) => outer,
+                                None if c == '}' => start,
+                                None => line,
+                            };
+                            interp.clear();
+                            self.errors.push(ScanError {
+                                line,
+                                tok: "Unterminated string.".into(),
+                            });
+                            continue;
+                        }
+                    }
+                }
+                '}' => {
+                    if let Some((depth, _)) = interp.last_mut() {
+                        *depth -= 1;
+                    }
+                    (TokenType::RightBrace, "}".into(), Literal::None)
+                }
+                '[' => (TokenType::LeftBracket, "[".into(), Literal::None),
+                ']' => (TokenType::RightBracket, "]".into(), Literal::None),
+                ':' => (TokenType::Colon, ":".into(), Literal::None),
                 ',' => (TokenType::Comma, ",".into(), Literal::None),
-                '.' => (TokenType::Dot, ".".into(), Literal::None),
-                '-' => (TokenType::Minus, "-".into(), Literal::None),
-                '+' => (TokenType::Plus, "+".into(), Literal::None),
+                '.' => {
+                    let mut ahead = iter.clone();
+                    if ahead.next() == Some(&'.') && ahead.next() == Some(&'.') {
+                        iter.nth(1);
+                        (TokenType::Ellipsis, "...".into(), Literal::None)
+                    } else if let Some(&'.') = iter.peek() {
+                        iter.next();
+                        (TokenType::DotDot, "..".into(), Literal::None)
+                    } else {
+                        (TokenType::Dot, ".".into(), Literal::None)
+                    }
+                }
+                '-' => {
+                    if let Some(&'-') = iter.peek() {
+                        iter.next();
+                        (TokenType::MinusMinus, "--".into(), Literal::None)
+                    } else if let Some(&'=') = iter.peek() {
+                        iter.next();
+                        (TokenType::MinusEqual, "-=".into(), Literal::None)
+                    } else {
+                        (TokenType::Minus, "-".into(), Literal::None)
+                    }
+                }
+                '+' => {
+                    if let Some(&'+') = iter.peek() {
+                        iter.next();
+                        (TokenType::PlusPlus, "++".into(), Literal::None)
+                    } else if let Some(&'=') = iter.peek() {
+                        iter.next();
+                        (TokenType::PlusEqual, "+=".into(), Literal::None)
+                    } else {
+                        (TokenType::Plus, "+".into(), Literal::None)
+                    }
+                }
+                '?' => {
+                    if let Some(&'.') = iter.peek() {
+                        iter.next();
+                        (TokenType::QuestionDot, "?.".into(), Literal::None)
+                    } else if let Some(&'?') = iter.peek() {
+                        iter.next();
+                        (TokenType::QuestionQuestion, "??".into(), Literal::None)
+                    } else {
+                        (TokenType::Question, "?".into(), Literal::None)
+                    }
+                }
                 ';' => (TokenType::Semicolon, ";".into(), Literal::None),
-                '*' => (TokenType::Star, "*".into(), Literal::None),
+                '*' => {
+                    if let Some(&'=') = iter.peek() {
+                        iter.next();
+                        (TokenType::StarEqual, "*=".into(), Literal::None)
+                    } else if let Some(&'*') = iter.peek() {
+                        iter.next();
+                        (TokenType::StarStar, "**".into(), Literal::None)
+                    } else {
+                        (TokenType::Star, "*".into(), Literal::None)
+                    }
+                }
                 '=' => {
                     if let Some(&'=') = iter.peek() {
                         iter.next();
                         (TokenType::EqualEqual, "==".into(), Literal::None)
+                    } else if let Some(&'>') = iter.peek() {
+                        iter.next();
+                        (TokenType::Arrow, "=>".into(), Literal::None)
                     } else {
                         (TokenType::Equal, "=".into(), Literal::None)
                     }
@@ -68,29 +212,23 @@ impl Scanner {
                         (TokenType::Less, "<".into(), Literal::None)
                     }
                 }
-                '"' => {
-                    let mut res = vec![];
-                    while let Some(&c) = iter.next_if(|&&x| x != '"') {
-                        res.push(c);
-                    }
-
-                    if let None = iter.next_if(|&&x| x == '"') {
-                        self.errors.push(ScanError {
-                            line,
-                            tok: "Unterminated string.".into(),
-                        });
-                        continue;
-                    }
-
-                    let s: String = res.into_iter().collect();
-
-                    (TokenType::String, format!("\"{s}\""), Literal::String(s))
-                }
+                '%' => (TokenType::Percent, "%".into(), Literal::None),
                 '/' => {
-                    if let Some(&'/') = iter.peek() {
+                    let offset = self.src.len() - iter.len() - 1;
+                    if iter.peek() == Some(&&'/')
+                        && ends_operand(self.tokens.last(), line)
+                        && !self.comments.contains(&offset)
+                    {
+                        iter.next();
+                        self.divisions.push((self.tokens.len(), offset));
+                        (TokenType::SlashSlash, "//".into(), Literal::None)
+                    } else if let Some(&'/') = iter.peek() {
                         iter.position(|&x| x == '\n');
                         line += 1;
                         continue;
+                    } else if let Some(&'=') = iter.peek() {
+                        iter.next();
+                        (TokenType::SlashEqual, "/=".into(), Literal::None)
                     } else {
                         (TokenType::Slash, "/".into(), Literal::None)
                     }
@@ -105,7 +243,11 @@ impl Scanner {
                         while let Some(&c) = iter.next_if(|&&x| x.is_numeric()) {
                             res.push(c);
                         }
-                        if Some(&&'.') == iter.peek() {
+                        // `1.` and `1..2` end the number before the dot
+                        let mut ahead = iter.clone();
+                        if ahead.next() == Some(&'.')
+                            && ahead.next().is_some_and(|x| x.is_numeric())
+                        {
                             res.push(*iter.next().unwrap());
                             while let Some(&c) = iter.next_if(|&&x| x.is_numeric()) {
                                 res.push(c);
@@ -143,6 +285,13 @@ impl Scanner {
             self.tokens.push(token);
         }
 
+        if let Some(&(_, start)) = interp.first() {
+            self.errors.push(ScanError {
+                line: start,
+                tok: "Unterminated string.".into(),
+            });
+        }
+
         self.tokens.push(Token::new(
             TokenType::Eof,
             "".to_string(),
@@ -150,4 +299,50 @@ impl Scanner {
             line,
         ));
     }
+
+    /// `open` is the quote or the `}` closing the previous interpolation.
+    fn string(
+        iter: &mut Peekable<Iter<'_, char>>,
+        line: &mut usize,
+        open: char,
+    ) -> Option<(TokenType, String, Literal)> {
+        let mut res = String::new();
+        while let Some(&c) = iter.next() {
+            match c {
+                '"' => {
+                    let eme = format!("{open}{res}\"");
+                    return Some((TokenType::String, eme, Literal::String(res)));
+                }
+                '$' if iter.peek() == Some(&&'{') => {
+                    iter.next();
+                    let eme = format!("{open}{res}${{");
+                    return Some((TokenType::Interpolation, eme, Literal::String(res)));
+                }
+                '\n' => {
+                    *line += 1;
+                    res.push(c);
+                }
+                c => res.push(c),
+            }
+        }
+        None
+    }
+}
+
+fn ends_operand(tok: Option<&Token>, line: usize) -> bool {
+    tok.is_some_and(|t| {
+        t.line == line
+            && matches!(
+                t.token_type,
+                TokenType::Number
+                    | TokenType::String
+                    | TokenType::Identifier
+                    | TokenType::RightParen
+                    | TokenType::RightBracket
+                    | TokenType::True
+                    | TokenType::False
+                    | TokenType::Nil
+                    | TokenType::This
+            )
+    })
 }
@@ -6,23 +6,42 @@ use convert_case::{Case, Casing};
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
 pub enum TokenType {
     // Single-character tokens
+    At,
     LeftParen,
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
     Comma,
     Dot,
     Minus,
+    Percent,
     Plus,
+    Question,
     Semicolon,
     Slash,
     Star,
 
-    // One or two character tokens.
+    // One or more character tokens.
+    DotDot,
+    Ellipsis,
+    MinusEqual,
+    MinusMinus,
+    PlusEqual,
+    PlusPlus,
+    SlashEqual,
+    StarEqual,
+    StarStar,
+    SlashSlash,
+    Arrow,
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    QuestionDot,
+    QuestionQuestion,
     Greater,
     GreaterEqual,
     Less,
@@ -31,25 +50,34 @@ pub enum TokenType {
     // Literals
     Identifier,
     String,
+    Interpolation,
     Number,
 
     // Keywords
     And,
+    Break,
+    Catch,
     Class,
+    Continue,
     Else,
     False,
+    Finally,
     Fun,
     For,
     If,
+    Import,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
+    Yield,
 
     Eof,
 }
@@ -136,19 +164,27 @@ impl Token {
 
 pub static KEYWORDS: phf::Map<&'static str, TokenType> = phf_map!(
     "and" => TokenType::And,
+    "break" => TokenType::Break,
+    "catch" => TokenType::Catch,
     "class" => TokenType::Class,
+    "continue" => TokenType::Continue,
     "else" => TokenType::Else,
     "false" => TokenType::False,
+    "finally" => TokenType::Finally,
     "for" => TokenType::For,
     "fun" => TokenType::Fun,
     "if" => TokenType::If,
+    "import" => TokenType::Import,
     "nil" => TokenType::Nil,
     "or" => TokenType::Or,
     "print" => TokenType::Print,
     "return" => TokenType::Return,
     "super" => TokenType::Super,
     "this" => TokenType::This,
+    "throw" => TokenType::Throw,
     "true" => TokenType::True,
+    "try" => TokenType::Try,
     "var" => TokenType::Var,
     "while" => TokenType::While,
+    "yield" => TokenType::Yield,
 );